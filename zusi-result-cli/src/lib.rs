@@ -6,8 +6,9 @@ use std::io::Read;
 use std::path::PathBuf;
 
 use glob::{glob, PatternError};
+use time::Duration;
 use zusi_result_lib::result_analyser::{AnalyseError, PureAverageSpeedAlgorithm, ResultAnalyser};
-use zusi_result_lib::result_analyser_group::{CreateAnalyserGroupError, ResultAnalyserGroup};
+use zusi_result_lib::result_analyser_group::{CreateAnalyserGroupError, ResultAnalyserGroup, TolerantAggregate};
 use zusi_xml_lib::xml::zusi::{DeError, Zusi, ZusiValue};
 use zusi_xml_lib::xml::zusi::result::ZusiResult;
 use crate::cli::AnalyseFilesArgs;
@@ -22,6 +23,7 @@ pub fn analyse_files(args: AnalyseFilesArgs) -> Result<(), AnalyseFilesError> {
     println!("Analyse files by pattern: {}", args.pattern);
 
     let mut results: Vec<ZusiResult> = vec![];
+    let mut paths: Vec<PathBuf> = vec![];
 
     for entry in glob(&args.pattern).map_err(|e| AnalyseFilesError::PatternError(e))? {
         match entry {
@@ -32,6 +34,7 @@ pub fn analyse_files(args: AnalyseFilesArgs) -> Result<(), AnalyseFilesError> {
                             println!("{:?}", path.display())
                         }
                         results.push(result);
+                        paths.push(path);
                     }
                     Err(ReadResultError::IOError(e)) => {
                         eprintln!("Error reading file '{:?}': {:?}", path, e);
@@ -53,7 +56,7 @@ pub fn analyse_files(args: AnalyseFilesArgs) -> Result<(), AnalyseFilesError> {
 
     println!();
     println!("Analysis results:");
-    print_analysis(results).map_err(|e| AnalyseFilesError::PrintAnalysisError(e))?;
+    print_analysis(results, &paths).map_err(|e| AnalyseFilesError::PrintAnalysisError(e))?;
     Ok(())
 }
 
@@ -80,25 +83,98 @@ fn read_result(path: &PathBuf) -> Result<ZusiResult, ReadResultError> {
 #[derive(Debug)]
 pub enum PrintAnalysisError {
     CreateAnalyserGroupError(CreateAnalyserGroupError),
+    #[deprecated(note = "results which can't be analysed are excluded from the analysis instead")]
     AnalyseError(AnalyseError),
 }
 
-fn print_analysis(results: Vec<ZusiResult>) -> Result<(), PrintAnalysisError> {
-    let mut analyser_group: ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult> = results.try_into().map_err(|e| PrintAnalysisError::CreateAnalyserGroupError(e))?;
-
-    println!("total distance: {} m", analyser_group.total_distance().map_err(|e| PrintAnalysisError::AnalyseError(e))?);
+/// The aggregates printed by [print_analysis].
+struct Summary {
+    total_distance: TolerantAggregate<f32>,
+    average_distance: TolerantAggregate<f32>,
+    average_speed: TolerantAggregate<f32>,
+    pure_average_speed: TolerantAggregate<f32>,
+    total_driving_time: TolerantAggregate<Duration>,
+    total_pure_driving_time: TolerantAggregate<Duration>,
+}
 
-    println!("average distance: {} m", analyser_group.average_distance().map_err(|e| PrintAnalysisError::AnalyseError(e))?);
+impl Summary {
+    fn new<A: AsRef<ResultAnalyser<ZusiResult>>>(analyser_group: &ResultAnalyserGroup<A, ZusiResult>) -> Summary {
+        Self {
+            total_distance: analyser_group.total_distance_tolerant(),
+            average_distance: analyser_group.average_distance_tolerant(),
+            average_speed: analyser_group.average_speed_tolerant(),
+            pure_average_speed: analyser_group.pure_average_speed_tolerant(PureAverageSpeedAlgorithm::default()),
+            total_driving_time: analyser_group.total_driving_time_tolerant(),
+            total_pure_driving_time: analyser_group.total_pure_driving_time_tolerant(),
+        }
+    }
 
-    let average_speed = analyser_group.average_speed().map_err(|e| PrintAnalysisError::AnalyseError(e))?;
-    println!("average speed: {} m/s = {} km/h", average_speed, average_speed * 3.6);
+    /// Returns each failing result once together with its first error.
+    fn failures(&self) -> Vec<(usize, AnalyseError)> {
+        let mut failures: Vec<(usize, AnalyseError)> = [
+            &self.total_distance.failures,
+            &self.average_distance.failures,
+            &self.average_speed.failures,
+            &self.pure_average_speed.failures,
+            &self.total_driving_time.failures,
+            &self.total_pure_driving_time.failures,
+        ]
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect();
+        failures.sort_by_key(|(index, _)| *index);
+        failures.dedup_by_key(|(index, _)| *index);
+        failures
+    }
 
-    let pure_average_speed = analyser_group.pure_average_speed(PureAverageSpeedAlgorithm::default()).map_err(|e| PrintAnalysisError::AnalyseError(e))?;
-    println!("pure average speed: {} m/s = {} km/h", pure_average_speed, pure_average_speed * 3.6);
+    fn print(&self) {
+        if let Some(total_distance) = self.total_distance.value {
+            println!("total distance: {} m", total_distance);
+        }
+        if let Some(average_distance) = self.average_distance.value {
+            println!("average distance: {} m", average_distance);
+        }
+        if let Some(average_speed) = self.average_speed.value {
+            println!("average speed: {} m/s = {} km/h", average_speed, average_speed * 3.6);
+        }
+        if let Some(pure_average_speed) = self.pure_average_speed.value {
+            println!("pure average speed: {} m/s = {} km/h", pure_average_speed, pure_average_speed * 3.6);
+        }
+        if let Some(total_driving_time) = self.total_driving_time.value {
+            println!("total driving time: {}", total_driving_time);
+        }
+        if let Some(total_pure_driving_time) = self.total_pure_driving_time.value {
+            println!("total pure driving time: {}", total_pure_driving_time);
+        }
+    }
+}
 
-    println!("total driving time: {}", analyser_group.total_driving_time().map_err(|e| PrintAnalysisError::AnalyseError(e))?);
+fn print_analysis(results: Vec<ZusiResult>, paths: &[PathBuf]) -> Result<(), PrintAnalysisError> {
+    let analyser_group: ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult> = results.try_into().map_err(|e| PrintAnalysisError::CreateAnalyserGroupError(e))?;
+
+    let summary = Summary::new(&analyser_group);
+    let failures = summary.failures();
+
+    if failures.is_empty() {
+        summary.print();
+    } else {
+        // a result failing for one aggregate is left out of all of them, so the totals match the excluded results
+        let valid_analysers: Vec<&ResultAnalyser<ZusiResult>> = analyser_group.analysers().iter().enumerate()
+            .filter(|(index, _)| !failures.iter().any(|(failing_index, _)| failing_index == index))
+            .map(|(_, analyser)| analyser)
+            .collect();
+        match ResultAnalyserGroup::new(valid_analysers) {
+            Ok(valid_group) => Summary::new(&valid_group).print(),
+            Err(CreateAnalyserGroupError::NoAnalysers) => println!("no valid results"),
+        }
 
-    println!("total pure driving time: {}", analyser_group.total_pure_driving_time().map_err(|e| PrintAnalysisError::AnalyseError(e))?);
+        println!();
+        println!("Excluded results:");
+        for (index, error) in failures {
+            println!("{:?}: {:?}", paths[index].display(), error);
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,269 @@
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+/// Classification of a [FahrtEintrag] by its `FahrtTyp`.
+/// The meaning of the values follows the entries Zusi 3 writes into `.result.xml` files.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum FahrtEventKind {
+    /// Regular recording of the driving state (`FahrtTyp` `0` or missing).
+    Recording,
+    /// Forced braking (Zwangsbremsung) by the train protection system (`1`).
+    ForcedBraking,
+    /// Stopping at or passing a timetable station (`2`).
+    Station,
+    /// Violation of operating rules like departing without order (`3`).
+    RuleViolation,
+    /// Remarks about the driving style like a strong jerk when stopping (`4`).
+    DrivingStyle,
+    /// Passing a signal (`5`).
+    Signal,
+    /// Beginning of a restrictive supervision by the train protection system (`6`).
+    TrainProtectionRestriction,
+    /// End of a restrictive supervision by the train protection system (`7`).
+    TrainProtectionRelease,
+    /// Operation of the train protection system by the driver (`8`).
+    TrainProtectionOperation,
+    /// Any other `FahrtTyp`.
+    Unknown(i32),
+}
+
+impl FahrtEventKind {
+    /// Returns the `FahrtTyp` value of this kind.
+    pub fn fahrt_typ(&self) -> i32 {
+        match self {
+            FahrtEventKind::Recording => 0,
+            FahrtEventKind::ForcedBraking => 1,
+            FahrtEventKind::Station => 2,
+            FahrtEventKind::RuleViolation => 3,
+            FahrtEventKind::DrivingStyle => 4,
+            FahrtEventKind::Signal => 5,
+            FahrtEventKind::TrainProtectionRestriction => 6,
+            FahrtEventKind::TrainProtectionRelease => 7,
+            FahrtEventKind::TrainProtectionOperation => 8,
+            FahrtEventKind::Unknown(fahrt_typ) => *fahrt_typ,
+        }
+    }
+}
+
+impl From<i32> for FahrtEventKind {
+    fn from(fahrt_typ: i32) -> Self {
+        match fahrt_typ {
+            0 => FahrtEventKind::Recording,
+            1 => FahrtEventKind::ForcedBraking,
+            2 => FahrtEventKind::Station,
+            3 => FahrtEventKind::RuleViolation,
+            4 => FahrtEventKind::DrivingStyle,
+            5 => FahrtEventKind::Signal,
+            6 => FahrtEventKind::TrainProtectionRestriction,
+            7 => FahrtEventKind::TrainProtectionRelease,
+            8 => FahrtEventKind::TrainProtectionOperation,
+            fahrt_typ => FahrtEventKind::Unknown(fahrt_typ),
+        }
+    }
+}
+
+/// Single flag of the `FahrtParameter` bitmask.
+/// The meaning of the flags depends on the [FahrtEventKind] they are recorded with.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum FahrtParameterFlag {
+    /// Lowest bit (`1`), which marks the departure from a station if recorded with [FahrtEventKind::Station].
+    /// It is also set on other kinds like [FahrtEventKind::Signal] and [FahrtEventKind::RuleViolation] with an unknown meaning.
+    Bit0,
+    /// Severe remark (`2`) like a strong jerk when stopping or departing without order.
+    Severe,
+    /// Remark about the driving style without further description (`4`).
+    DrivingStyle,
+    /// Train protection button "Wachsam" (`16`).
+    PzbWachsam,
+    /// Train protection button "Wachsam" held down (`32`).
+    PzbWachsamHeld,
+    /// Train protection button "Frei" (`64`).
+    PzbFrei,
+    /// Train protection button "Frei" held down (`128`).
+    PzbFreiHeld,
+}
+
+impl FahrtParameterFlag {
+    pub const ALL: [FahrtParameterFlag; 7] = [
+        FahrtParameterFlag::Bit0,
+        FahrtParameterFlag::Severe,
+        FahrtParameterFlag::DrivingStyle,
+        FahrtParameterFlag::PzbWachsam,
+        FahrtParameterFlag::PzbWachsamHeld,
+        FahrtParameterFlag::PzbFrei,
+        FahrtParameterFlag::PzbFreiHeld,
+    ];
+
+    /// Returns the bit of this flag within `FahrtParameter`.
+    pub fn bits(&self) -> u32 {
+        match self {
+            FahrtParameterFlag::Bit0 => 1,
+            FahrtParameterFlag::Severe => 2,
+            FahrtParameterFlag::DrivingStyle => 4,
+            FahrtParameterFlag::PzbWachsam => 16,
+            FahrtParameterFlag::PzbWachsamHeld => 32,
+            FahrtParameterFlag::PzbFrei => 64,
+            FahrtParameterFlag::PzbFreiHeld => 128,
+        }
+    }
+}
+
+/// Decoded `FahrtParameter` bitmask.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, Default)]
+pub struct FahrtParameterFlags(u32);
+
+impl FahrtParameterFlags {
+    pub fn from_bits(bits: u32) -> FahrtParameterFlags {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, flag: FahrtParameterFlag) -> bool {
+        self.0 & flag.bits() != 0
+    }
+
+    /// Iterates over all known flags which are set.
+    pub fn iter(&self) -> impl Iterator<Item = FahrtParameterFlag> + '_ {
+        FahrtParameterFlag::ALL.into_iter().filter(|flag| self.contains(*flag))
+    }
+
+    /// Returns all set bits which do not belong to a known [FahrtParameterFlag].
+    pub fn unknown_bits(&self) -> u32 {
+        FahrtParameterFlag::ALL.iter().fold(self.0, |bits, flag| bits & !flag.bits())
+    }
+}
+
+impl From<i32> for FahrtParameterFlags {
+    fn from(fahrt_parameter: i32) -> Self {
+        Self::from_bits(fahrt_parameter as u32)
+    }
+}
+
+/// Position of a [FahrtEvent] on the route.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct EventPosition {
+    /// Driven distance since the start of the route in m.
+    pub fahrt_weg: f32,
+    /// Kilometre post of the route in km.
+    pub fahrt_km: f32,
+}
+
+/// Typed view of a single [FahrtEintrag].
+#[derive(PartialEq, Debug, Clone)]
+pub struct FahrtEvent<'a> {
+    pub kind: FahrtEventKind,
+    pub flags: FahrtParameterFlags,
+    pub time: PrimitiveDateTime,
+    /// [None] if the entry does not record a position (`FahrtWeg` is `-1`).
+    pub position: Option<EventPosition>,
+    /// [None] if the entry does not contain a `FahrtText`.
+    pub text: Option<&'a str>,
+    pub fahrt_eintrag: &'a FahrtEintrag,
+}
+
+impl<'a> From<&'a FahrtEintrag> for FahrtEvent<'a> {
+    fn from(fahrt_eintrag: &'a FahrtEintrag) -> Self {
+        Self {
+            kind: fahrt_eintrag.fahrt_typ.into(),
+            flags: fahrt_eintrag.fahrt_parameter.into(),
+            time: fahrt_eintrag.fahrt_zeit,
+            position: if fahrt_eintrag.fahrt_weg == -1. {
+                None
+            } else {
+                Some(EventPosition {
+                    fahrt_weg: fahrt_eintrag.fahrt_weg,
+                    fahrt_km: fahrt_eintrag.fahrt_km,
+                })
+            },
+            text: if fahrt_eintrag.fahrt_text.is_empty() {
+                None
+            } else {
+                Some(&fahrt_eintrag.fahrt_text)
+            },
+            fahrt_eintrag,
+        }
+    }
+}
+
+/// Iterates over all [FahrtEintrag](ResultValue::FahrtEintrag) entries of a [ZusiResult] as [FahrtEvent]s.
+pub fn fahrt_events(result: &ZusiResult) -> impl Iterator<Item = FahrtEvent<'_>> {
+    result.value.iter().map(|ResultValue::FahrtEintrag(fahrt_eintrag)| fahrt_eintrag.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_fahrt_event_kind() {
+        for fahrt_typ in 0..10 {
+            assert_eq!(FahrtEventKind::from(fahrt_typ).fahrt_typ(), fahrt_typ);
+        }
+        assert_eq!(FahrtEventKind::from(5), FahrtEventKind::Signal);
+        assert_eq!(FahrtEventKind::from(9), FahrtEventKind::Unknown(9));
+    }
+
+    #[test]
+    fn test_fahrt_parameter_flags() {
+        let flags = FahrtParameterFlags::from(192);
+        assert!(flags.contains(FahrtParameterFlag::PzbFrei));
+        assert!(flags.contains(FahrtParameterFlag::PzbFreiHeld));
+        assert!(!flags.contains(FahrtParameterFlag::PzbWachsam));
+        assert_eq!(flags.iter().collect::<Vec<_>>(), vec![FahrtParameterFlag::PzbFrei, FahrtParameterFlag::PzbFreiHeld]);
+        assert_eq!(flags.unknown_bits(), 0);
+
+        let flags = FahrtParameterFlags::from(3 | 8 | 256);
+        assert_eq!(flags.iter().collect::<Vec<_>>(), vec![FahrtParameterFlag::Bit0, FahrtParameterFlag::Severe]);
+        assert_eq!(flags.unknown_bits(), 8 | 256);
+
+        assert!(FahrtParameterFlags::default().is_empty());
+    }
+
+    #[test]
+    fn test_fahrt_events() {
+        let result = ZusiResult::builder()
+            .datum(datetime!(2019-01-01 23:14))
+            .value(vec![
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_weg(2.33)
+                    .fahrt_zeit(datetime!(2019-01-01 23:18))
+                    .fahrt_km(12.5)
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_typ(5)
+                    .fahrt_weg(22.43)
+                    .fahrt_zeit(datetime!(2019-01-01 23:19))
+                    .fahrt_km(12.48)
+                    .fahrt_text("Sbk 410 410".into())
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_typ(3)
+                    .fahrt_weg(-1.)
+                    .fahrt_zeit(datetime!(2019-01-01 23:20))
+                    .fahrt_speed(-1.)
+                    .fahrt_text("Abfahrt ohne Auftrag oder vor Abfahrtzeit".into())
+                    .build()),
+            ])
+            .build();
+
+        let events: Vec<FahrtEvent> = fahrt_events(&result).collect();
+        let summary: Vec<(FahrtEventKind, PrimitiveDateTime, Option<EventPosition>, Option<&str>)> = events.iter()
+            .map(|event| (event.kind, event.time, event.position, event.text))
+            .collect();
+
+        assert_eq!(summary, vec![
+            (FahrtEventKind::Recording, datetime!(2019-01-01 23:18), Some(EventPosition { fahrt_weg: 2.33, fahrt_km: 12.5 }), None),
+            (FahrtEventKind::Signal, datetime!(2019-01-01 23:19), Some(EventPosition { fahrt_weg: 22.43, fahrt_km: 12.48 }), Some("Sbk 410 410")),
+            (FahrtEventKind::RuleViolation, datetime!(2019-01-01 23:20), None, Some("Abfahrt ohne Auftrag oder vor Abfahrtzeit")),
+        ]);
+    }
+}
@@ -5,4 +5,7 @@
 pub mod result_analyser;
 
 /// Contains everything for analysing multiple `.result.xml` files by aggregating the single results.
-pub mod result_analyser_group;
\ No newline at end of file
+pub mod result_analyser_group;
+
+/// Contains a typed view of the single entries of a `.result.xml` file.
+pub mod fahrt_event;
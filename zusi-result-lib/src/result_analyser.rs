@@ -1,60 +1,164 @@
-use crate::result_analyser::helpers::filter_valid_fahrt_weg_and_fahrt_speed;
+use crate::fahrt_event::{fahrt_events, FahrtEvent, FahrtEventKind, FahrtParameterFlag};
+use crate::result_analyser::acceleration::{find_acceleration_profile, AccelerationProfile};
+use crate::result_analyser::analyser_cache::AnalyserCache;
+use crate::result_analyser::analysis_options::{AnalysisOptions, StandstillOptions};
+use crate::result_analyser::helpers::{filter_valid_indices, find_driving_intervals};
+use crate::result_analyser::emergency_braking::{find_emergency_brakings, EmergencyBraking, EmergencyBrakingKind};
+use crate::result_analyser::histogram::Histogram;
+use crate::result_analyser::minimum_run_time::find_minimum_run_time;
+use crate::result_analyser::punctuality::{find_punctuality, Punctuality};
+use crate::result_analyser::section::{find_sections, Section};
+use crate::result_analyser::signal_restriction::{find_signal_restrictions, SignalRestrictions};
+use crate::result_analyser::train_protection::{find_train_protection, TrainProtection};
+use crate::result_analyser::speed_distribution::{find_speed_percentiles, weighted_local_speeds, SpeedPercentiles, SpeedWeighting};
+use crate::result_analyser::speed_violation::{find_speed_violations, SpeedViolation};
+use crate::result_analyser::standstill::{find_standstill_periods, StandstillPeriod};
+use crate::result_analyser::validation::{ValidationOptions, ValidationReport};
+use crate::result_analyser::stop::{find_stops, Stop};
+use std::collections::HashMap;
 use time::Duration;
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
 
+/// Zusi records the energy consumption in Ws.
+const WS_PER_KWH: f32 = 3_600_000.;
+
 #[cfg(test)]
 mod tests;
 mod helpers;
+mod analyser_cache;
+pub mod analysis_options;
+pub mod histogram;
+pub mod acceleration;
+pub mod speed_distribution;
+pub mod speed_violation;
+pub mod punctuality;
+pub mod stop;
+pub mod standstill;
+pub mod section;
+pub mod validation;
+pub mod signal_restriction;
+pub mod train_protection;
+pub mod emergency_braking;
+pub mod minimum_run_time;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Copy, Clone)]
 pub enum AnalyseError {
     NoEntries,
     ZeroDistance,
     ZeroDrivingTime,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Debug)]
 pub struct ResultAnalyser<R> {
     result: R,
-    // TODO: implement cache
+    options: AnalysisOptions,
+    cache: AnalyserCache,
+}
+
+/// Analysers are equal if they analyse equal results with equal options, regardless of which metrics have been cached.
+impl<R: PartialEq> PartialEq for ResultAnalyser<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.result == other.result && self.options == other.options
+    }
 }
 
 impl<R: AsRef<ZusiResult>> ResultAnalyser<R> {
     pub fn new(result: R) -> ResultAnalyser<R> {
+        Self::new_with_options(result, AnalysisOptions::default())
+    }
+
+    pub fn new_with_options(result: R, options: AnalysisOptions) -> ResultAnalyser<R> {
         Self {
             result,
+            options,
+            cache: AnalyserCache::new(),
         }
     }
 
-    /// Computes the distance for the whole route by using the `fahrt_weg` attribute.
+    pub fn options(&self) -> &AnalysisOptions {
+        &self.options
+    }
+
+    pub fn result(&self) -> &ZusiResult {
+        self.result.as_ref()
+    }
+
+    /// Returns all entries which are valid according to the [ValidityPolicy](analysis_options::ValidityPolicy).
+    /// The entries are only filtered once, later calls use the cached indices.
+    fn filtered_values(&self) -> Vec<&ResultValue> {
+        let result = self.result.as_ref();
+        self.cache.filtered_indices
+            .get_or_init(|| filter_valid_indices(result, &self.options.validity))
+            .iter()
+            .map(|index| &result.value[*index])
+            .collect()
+    }
+
+    /// Checks all [FahrtEintrag](ResultValue::FahrtEintrag) entries for suspicious data which may distort the metrics.
+    /// Positions and speeds are only checked for entries which are valid according to the [ValidityPolicy](analysis_options::ValidityPolicy).
+    /// For more details see [AnomalyKind](validation::AnomalyKind).
     ///
     /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
-    pub fn distance(&self) -> Result<f32, AnalyseError> {
+    pub fn validate(&self, options: &ValidationOptions) -> Result<ValidationReport, AnalyseError> {
         let result = self.result.as_ref();
 
-        // also need to filter fahrt_speed because of usage in pure_average_speed_by_pure_driving_time
-        let filtered_values = filter_valid_fahrt_weg_and_fahrt_speed(result);
-
-        if filtered_values.len() > 0 {
-            let ResultValue::FahrtEintrag(first) = filtered_values.first().unwrap();
-            let ResultValue::FahrtEintrag(last) = filtered_values.last().unwrap();
-            Ok(last.fahrt_weg - first.fahrt_weg)
-        } else {
+        if result.value.is_empty() {
             Err(AnalyseError::NoEntries)
+        } else {
+            Ok(validation::validate(result, &self.options.validity, options))
         }
     }
 
+    /// Iterates over all [FahrtEintrag](ResultValue::FahrtEintrag) entries classified by their `FahrtTyp`.
+    /// For more details see [FahrtEvent].
+    pub fn events(&self) -> impl Iterator<Item = FahrtEvent<'_>> {
+        fahrt_events(self.result.as_ref())
+    }
+
+    /// Counts how often each [FahrtParameterFlag] is set among all [FahrtEvent]s.
+    /// As the meaning of a flag depends on the `FahrtTyp`, the flags are counted separately for each [FahrtEventKind].
+    /// Flags which are never set are omitted.
+    pub fn fahrt_parameter_flag_counts(&self) -> HashMap<(FahrtEventKind, FahrtParameterFlag), usize> {
+        let mut counts = HashMap::new();
+        for event in self.events() {
+            for flag in event.flags.iter() {
+                *counts.entry((event.kind, flag)).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Computes the distance for the whole route by using the `fahrt_weg` attribute.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn distance(&self) -> Result<f32, AnalyseError> {
+        *self.cache.distance.get_or_init(|| {
+            // also need to filter fahrt_speed because of usage in pure_average_speed_by_pure_driving_time
+            let filtered_values = self.filtered_values();
+
+            if filtered_values.len() > 0 {
+                let ResultValue::FahrtEintrag(first) = filtered_values.first().unwrap();
+                let ResultValue::FahrtEintrag(last) = filtered_values.last().unwrap();
+                Ok(last.fahrt_weg - first.fahrt_weg)
+            } else {
+                Err(AnalyseError::NoEntries)
+            }
+        })
+    }
+
     /// Computes the average speed including idle times by using the overall driving time and distance.
     ///
     /// Throws [AnalyseError::ZeroDrivingTime] if the computed driving time is zero.
     pub fn average_speed(&self) -> Result<f32, AnalyseError> {
-        let distance = self.distance()?;
-        let driving_time = self.driving_time()?.as_seconds_f32();
-        if driving_time == 0.0 {
-            Err(AnalyseError::ZeroDrivingTime)
-        } else {
-            Ok(distance / driving_time)
-        }
+        *self.cache.average_speed.get_or_init(|| {
+            let distance = self.distance()?;
+            let driving_time = self.driving_time()?.as_seconds_f32();
+            if driving_time == 0.0 {
+                Err(AnalyseError::ZeroDrivingTime)
+            } else {
+                Ok(distance / driving_time)
+            }
+        })
     }
 
     /// Computes the average speed excluding idle times.
@@ -65,6 +169,37 @@ impl<R: AsRef<ZusiResult>> ResultAnalyser<R> {
         match algorithm {
             PureAverageSpeedAlgorithm::PureDrivingTime => self.pure_average_speed_by_pure_driving_time(),
             PureAverageSpeedAlgorithm::WeightedLocalSpeeds => self.pure_average_speed_by_weighted_local_speeds(),
+            PureAverageSpeedAlgorithm::HarmonicLocalSpeeds => self.pure_average_speed_by_harmonic_local_speeds(),
+            PureAverageSpeedAlgorithm::MedianLocalSpeed => self.pure_average_speed_by_median_local_speed(),
+        }
+    }
+
+    /// Computes the average speed excluding idle times with all [PureAverageSpeedAlgorithm] variants,
+    /// so that their results can be compared.
+    pub fn pure_average_speeds(&self) -> PureAverageSpeeds {
+        PureAverageSpeeds {
+            values: PureAverageSpeedAlgorithm::ALL.iter()
+                .map(|algorithm| (*algorithm, self.pure_average_speed(*algorithm)))
+                .collect(),
+        }
+    }
+
+    /// Computes the average speed excluding idle times like [pure_average_speed](ResultAnalyser::pure_average_speed),
+    /// but detects idle times with the given [StandstillOptions] instead of the ones of the [AnalysisOptions].
+    /// The options are not part of [PureAverageSpeedAlgorithm], as the algorithm is used as a cache key
+    /// and has to stay `Eq` and `Hash`, which the `f32` threshold of the options can't provide.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDrivingTime] or [AnalyseError::ZeroDistance] depending on selected algorithm.
+    pub fn pure_average_speed_with_standstill(&self, algorithm: PureAverageSpeedAlgorithm, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
+        if *standstill == self.options.standstill {
+            return self.pure_average_speed(algorithm);
+        }
+        match algorithm {
+            PureAverageSpeedAlgorithm::PureDrivingTime => self.compute_pure_average_speed_by_pure_driving_time(standstill),
+            PureAverageSpeedAlgorithm::WeightedLocalSpeeds => self.compute_pure_average_speed_by_weighted_local_speeds(standstill),
+            PureAverageSpeedAlgorithm::HarmonicLocalSpeeds => self.compute_pure_average_speed_by_harmonic_local_speeds(standstill),
+            PureAverageSpeedAlgorithm::MedianLocalSpeed => self.compute_pure_average_speed_by_median_local_speed(standstill),
         }
     }
 
@@ -73,8 +208,14 @@ impl<R: AsRef<ZusiResult>> ResultAnalyser<R> {
     /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
     /// Throws [AnalyseError::ZeroDrivingTime] if the time driven is zero.
     pub fn pure_average_speed_by_pure_driving_time(&self) -> Result<f32, AnalyseError> {
+        *self.cache.pure_average_speed_by_pure_driving_time.get_or_init(||
+            self.compute_pure_average_speed_by_pure_driving_time(&self.options.standstill)
+        )
+    }
+
+    fn compute_pure_average_speed_by_pure_driving_time(&self, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
         let distance = self.distance()?;
-        let pure_driving_time = self.pure_driving_time()?.as_seconds_f32();
+        let pure_driving_time = self.pure_driving_time_with_standstill(standstill)?.as_seconds_f32();
         if pure_driving_time == 0.0 {
             Err(AnalyseError::ZeroDrivingTime)
         } else {
@@ -89,66 +230,483 @@ impl<R: AsRef<ZusiResult>> ResultAnalyser<R> {
     /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
     /// Throws [AnalyseError::ZeroDistance] if the distance driven is zero.
     pub fn pure_average_speed_by_weighted_local_speeds(&self) -> Result<f32, AnalyseError> {
-        let result = self.result.as_ref();
+        *self.cache.pure_average_speed_by_weighted_local_speeds.get_or_init(||
+            self.compute_pure_average_speed_by_weighted_local_speeds(&self.options.standstill)
+        )
+    }
 
-        let filtered_values = filter_valid_fahrt_weg_and_fahrt_speed(result);
+    fn compute_pure_average_speed_by_weighted_local_speeds(&self, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
+        let filtered_values = self.filtered_values();
 
         if self.distance()? == 0. {
             Err(AnalyseError::ZeroDistance)
         } else if filtered_values.len() > 1 {
+            let driving_intervals = find_driving_intervals(&filtered_values, standstill);
             let mut weighted_speed_sum = 0.;
-            for i in 0..filtered_values.len() - 1 {
-                let ResultValue::FahrtEintrag(current) = filtered_values.get(i).unwrap();
-                let ResultValue::FahrtEintrag(next) = filtered_values.get(i + 1).unwrap();
+            for (pair, driving) in filtered_values.windows(2).zip(driving_intervals) {
+                if !driving {
+                    continue;
+                }
+                let ResultValue::FahrtEintrag(current) = pair[0];
+                let ResultValue::FahrtEintrag(next) = pair[1];
                 let local_average_speed = (current.fahrt_speed + next.fahrt_speed) / 2.;
                 let local_driving_time = next.fahrt_zeit - current.fahrt_zeit;
                 weighted_speed_sum += local_driving_time.as_seconds_f32() * local_average_speed;
             }
-            Ok(weighted_speed_sum / self.pure_driving_time()?.as_seconds_f32())
+            Ok(weighted_speed_sum / self.pure_driving_time_with_standstill(standstill)?.as_seconds_f32())
         } else {
             Err(AnalyseError::NoEntries)
         }
     }
 
+    /// Computes the average speed excluding idle times by integrating over the distance.
+    /// For each two [FahrtEintrag](ResultValue::FahrtEintrag) entries, the average speed between is computed.
+    /// The harmonic mean of these local average speeds weighted by their individual local distance is returned,
+    /// which is the distance driven divided by the time needed at the local speeds.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDistance] if the distance driven is zero.
+    pub fn pure_average_speed_by_harmonic_local_speeds(&self) -> Result<f32, AnalyseError> {
+        *self.cache.pure_average_speed_by_harmonic_local_speeds.get_or_init(||
+            self.compute_pure_average_speed_by_harmonic_local_speeds(&self.options.standstill)
+        )
+    }
+
+    fn compute_pure_average_speed_by_harmonic_local_speeds(&self, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
+        self.distance()?;
+        let filtered_values = self.filtered_values();
+
+        let mut distance = 0.;
+        let mut time = 0.;
+        for (pair, driving) in filtered_values.windows(2).zip(find_driving_intervals(&filtered_values, standstill)) {
+            let ResultValue::FahrtEintrag(current) = pair[0];
+            let ResultValue::FahrtEintrag(next) = pair[1];
+            let local_average_speed = (current.fahrt_speed + next.fahrt_speed) / 2.;
+            let local_distance = next.fahrt_weg - current.fahrt_weg;
+            if driving && local_average_speed > 0. && local_distance > 0. {
+                distance += local_distance;
+                time += local_distance / local_average_speed;
+            }
+        }
+
+        if distance == 0. {
+            Err(AnalyseError::ZeroDistance)
+        } else {
+            Ok(distance / time)
+        }
+    }
+
+    /// Computes the average speed excluding idle times as the median of the local average speeds.
+    /// For each two [FahrtEintrag](ResultValue::FahrtEintrag) entries while driving, the average speed between is computed.
+    /// Each of these local average speeds counts once regardless of its duration, so short outliers have no effect.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDrivingTime] if there are no local average speeds while driving.
+    pub fn pure_average_speed_by_median_local_speed(&self) -> Result<f32, AnalyseError> {
+        *self.cache.pure_average_speed_by_median_local_speed.get_or_init(||
+            self.compute_pure_average_speed_by_median_local_speed(&self.options.standstill)
+        )
+    }
+
+    fn compute_pure_average_speed_by_median_local_speed(&self, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
+        self.distance()?;
+        let filtered_values = self.filtered_values();
+
+        let mut local_average_speeds: Vec<f32> = filtered_values.windows(2)
+            .zip(find_driving_intervals(&filtered_values, standstill))
+            .filter(|(_, driving)| *driving)
+            .map(|(pair, _)| {
+                let ResultValue::FahrtEintrag(current) = pair[0];
+                let ResultValue::FahrtEintrag(next) = pair[1];
+                (current.fahrt_speed + next.fahrt_speed) / 2.
+            })
+            .collect();
+        local_average_speeds.sort_by(|a, b| a.total_cmp(b));
+
+        let count = local_average_speeds.len();
+        // usize::is_multiple_of would raise the minimum supported Rust version to 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        if count == 0 {
+            Err(AnalyseError::ZeroDrivingTime)
+        } else if count % 2 == 0 {
+            Ok((local_average_speeds[count / 2 - 1] + local_average_speeds[count / 2]) / 2.)
+        } else {
+            Ok(local_average_speeds[count / 2])
+        }
+    }
+
     /// Computes the whole driving time including idle times by using the `fahrt_zeit` attribute.
+    /// Uses the first and the last entry unless [filter_driving_time](AnalysisOptions::filter_driving_time) is set,
+    /// in which case the first and the last valid entry are used.
     ///
     /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
     pub fn driving_time(&self) -> Result<Duration, AnalyseError> {
-        let result = self.result.as_ref();
-        if result.value.len() > 0 {
-            let ResultValue::FahrtEintrag(first) = result.value.first().unwrap();
-            let ResultValue::FahrtEintrag(last) = result.value.last().unwrap();
-            Ok(last.fahrt_zeit - first.fahrt_zeit)
+        *self.cache.driving_time.get_or_init(|| {
+            let values = self.driving_time_values();
+            if values.len() > 0 {
+                let ResultValue::FahrtEintrag(first) = values.first().unwrap();
+                let ResultValue::FahrtEintrag(last) = values.last().unwrap();
+                Ok(last.fahrt_zeit - first.fahrt_zeit)
+            } else {
+                Err(AnalyseError::NoEntries)
+            }
+        })
+    }
+
+    /// Computes the whole driving time including idle times like [driving_time](ResultAnalyser::driving_time),
+    /// but omits all periods between two entries which are longer than `max_gap` or negative.
+    /// These are usually caused by pausing the simulation or by time jumps.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn driving_time_excluding_gaps(&self, max_gap: Duration) -> Result<Duration, AnalyseError> {
+        let values = self.driving_time_values();
+
+        if values.is_empty() {
+            return Err(AnalyseError::NoEntries);
+        }
+
+        let mut driving_time = Duration::seconds(0);
+        for pair in values.windows(2) {
+            let ResultValue::FahrtEintrag(current) = pair[0];
+            let ResultValue::FahrtEintrag(next) = pair[1];
+            let local_driving_time = next.fahrt_zeit - current.fahrt_zeit;
+            if !local_driving_time.is_negative() && local_driving_time <= max_gap {
+                driving_time += local_driving_time;
+            }
+        }
+        Ok(driving_time)
+    }
+
+    /// Computes the whole driving time including idle times.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn driving_time_with_algorithm(&self, algorithm: DrivingTimeAlgorithm) -> Result<Duration, AnalyseError> {
+        match algorithm {
+            DrivingTimeAlgorithm::FirstToLastEntry => self.driving_time(),
+            DrivingTimeAlgorithm::ExcludingGaps(max_gap) => self.driving_time_excluding_gaps(max_gap),
+        }
+    }
+
+    /// Returns the entries used for [driving_time](ResultAnalyser::driving_time)
+    /// depending on [filter_driving_time](AnalysisOptions::filter_driving_time).
+    fn driving_time_values(&self) -> Vec<&ResultValue> {
+        if self.options.filter_driving_time {
+            self.filtered_values()
         } else {
-            Err(AnalyseError::NoEntries)
+            self.result.as_ref().value.iter().collect()
         }
     }
 
-    /// Computes the whole driving time excluding idle times by omitting all periods with zero driving speed.
+    /// Computes the whole driving time excluding idle times by omitting all periods without driving speed.
+    /// The train is considered standing if the driving speed does not exceed the
+    /// [speed_threshold](StandstillOptions::speed_threshold) of the [AnalysisOptions].
+    /// Standstills shorter than [min_duration](StandstillOptions::min_duration) are counted as driving.
     ///
     /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
     pub fn pure_driving_time(&self) -> Result<Duration, AnalyseError> {
-        let result = self.result.as_ref();
+        *self.cache.pure_driving_time.get_or_init(||
+            self.compute_pure_driving_time(&self.options.standstill)
+        )
+    }
 
+    /// Computes the whole driving time excluding idle times like [pure_driving_time](ResultAnalyser::pure_driving_time),
+    /// but detects idle times with the given [StandstillOptions] instead of the ones of the [AnalysisOptions].
+    /// The options are not part of [PureAverageSpeedAlgorithm], as the algorithm is used as a cache key
+    /// and has to stay `Eq` and `Hash`, which the `f32` threshold of the options can't provide.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn pure_driving_time_with_standstill(&self, standstill: &StandstillOptions) -> Result<Duration, AnalyseError> {
+        if *standstill == self.options.standstill {
+            self.pure_driving_time()
+        } else {
+            self.compute_pure_driving_time(standstill)
+        }
+    }
+
+    fn compute_pure_driving_time(&self, standstill: &StandstillOptions) -> Result<Duration, AnalyseError> {
         // also need to filter fahrt_weg because of usage in pure_average_speed_by_pure_driving_time
-        let filtered_values = filter_valid_fahrt_weg_and_fahrt_speed(result);
+        let filtered_values = self.filtered_values();
 
         if filtered_values.len() > 1 {
+            let driving_intervals = find_driving_intervals(&filtered_values, standstill);
             let mut driving_time = Duration::seconds(0);
-            for i in 0..filtered_values.len() - 1 {
-                let ResultValue::FahrtEintrag(current) = filtered_values.get(i).unwrap();
-                let ResultValue::FahrtEintrag(next) = filtered_values.get(i + 1).unwrap();
-                if current.fahrt_speed > 0. || next.fahrt_speed > 0. {
+            for (pair, driving) in filtered_values.windows(2).zip(driving_intervals) {
+                if driving {
+                    let ResultValue::FahrtEintrag(current) = pair[0];
+                    let ResultValue::FahrtEintrag(next) = pair[1];
                     driving_time += next.fahrt_zeit - current.fahrt_zeit;
                 }
             }
             Ok(driving_time)
-        } else if result.value.len() > 0 {
+        } else if self.result.as_ref().value.len() > 0 {
             Ok(Duration::seconds(0))
         } else {
             Err(AnalyseError::NoEntries)
         }
     }
+
+    /// Returns the total energy consumption in kWh by using the `Verbrauch` attribute.
+    pub fn energy_consumption(&self) -> f32 {
+        self.result.as_ref().verbrauch / WS_PER_KWH
+    }
+
+    /// Computes the driving time of an ideal train which drives as fast as permitted over the same distance.
+    /// The permitted speed is the minimum of `FahrtspStrecke`, `FahrtspSignal` and `FahrtspZugsicherung`,
+    /// the ideal train accelerates and brakes as configured by [minimum_run_time](AnalysisOptions::minimum_run_time)
+    /// and stops wherever the recorded train stopped.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn minimum_run_time(&self) -> Result<Duration, AnalyseError> {
+        *self.cache.minimum_run_time.get_or_init(|| {
+            let filtered_values = self.filtered_values();
+
+            if filtered_values.is_empty() {
+                Err(AnalyseError::NoEntries)
+            } else {
+                Ok(find_minimum_run_time(&filtered_values, &self.options.minimum_run_time, &self.options.standstill))
+            }
+        })
+    }
+
+    /// Computes the time the driver could have saved compared with the [minimum_run_time](ResultAnalyser::minimum_run_time)
+    /// by subtracting it from the [pure_driving_time](ResultAnalyser::pure_driving_time).
+    /// The reserve may be negative if the recorded train was faster than the ideal train.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn run_time_reserve(&self) -> Result<Duration, AnalyseError> {
+        Ok(self.pure_driving_time()? - self.minimum_run_time()?)
+    }
+
+    /// Computes the energy consumption in kWh per km driven.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDistance] if the distance driven is zero.
+    pub fn energy_consumption_per_distance(&self) -> Result<f32, AnalyseError> {
+        *self.cache.energy_consumption_per_distance.get_or_init(|| {
+            let distance = self.distance()?;
+            if distance == 0. {
+                Err(AnalyseError::ZeroDistance)
+            } else {
+                Ok(self.energy_consumption() / (distance / 1000.))
+            }
+        })
+    }
+
+    /// Computes the energy consumption in kWh per hour of driving excluding idle times.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDrivingTime] if the time driven is zero.
+    pub fn energy_consumption_per_pure_driving_time(&self) -> Result<f32, AnalyseError> {
+        *self.cache.energy_consumption_per_pure_driving_time.get_or_init(|| {
+            let pure_driving_time = self.pure_driving_time()?.as_seconds_f32();
+            if pure_driving_time == 0. {
+                Err(AnalyseError::ZeroDrivingTime)
+            } else {
+                Ok(self.energy_consumption() / (pure_driving_time / 3600.))
+            }
+        })
+    }
+
+    /// Lists all standstills which are omitted by [pure_driving_time](ResultAnalyser::pure_driving_time).
+    /// Each period is linked to the nearest entry with a `FahrtText` of any kind, like a signal or a station.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn standstill_periods(&self) -> Result<Vec<StandstillPeriod>, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_standstill_periods(&filtered_values, &self.options.standstill))
+        }
+    }
+
+    /// Lists all standstills which are omitted by [pure_driving_time](ResultAnalyser::pure_driving_time).
+    /// Each standstill is linked to the nearest station.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn stops(&self) -> Result<Vec<Stop>, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_stops(&filtered_values, &self.options.standstill))
+        }
+    }
+
+    /// Finds all periods in which the driving speed exceeded the permitted speed.
+    /// The permitted speed is the minimum of `FahrtspStrecke`, `FahrtspSignal` and `FahrtspZugsicherung`.
+    /// For more details see [permitted_speed](speed_violation::permitted_speed).
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn speed_violations(&self) -> Result<Vec<SpeedViolation>, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_speed_violations(&filtered_values))
+        }
+    }
+
+    /// Finds all periods in which a signal restricted the permitted speed (`FahrtspSignal`)
+    /// and computes the time lost compared with driving at the permitted speed of the route (`FahrtspStrecke`).
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn signal_restrictions(&self) -> Result<SignalRestrictions, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_signal_restrictions(&filtered_values, &self.options.standstill))
+        }
+    }
+
+    /// Splits the route into episodes with the same speed supervised by the train protection system (`FahrtspZugsicherung`).
+    /// Each episode is classified by `supervision_speeds` in m/s,
+    /// [DEFAULT_SUPERVISION_SPEEDS](train_protection::DEFAULT_SUPERVISION_SPEEDS) can be used as a default for the PZB.
+    /// Additionally, the activations of the restrictive mode are counted.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn train_protection(&self, supervision_speeds: &[f32]) -> Result<TrainProtection, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_train_protection(&filtered_values, supervision_speeds))
+        }
+    }
+
+    /// Compares the timetable of all stations with the actual arrival and departure times.
+    /// Arrival and departure are determined by the standstill at the station entry.
+    /// For more details see [Punctuality].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn punctuality(&self) -> Result<Punctuality, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_punctuality(&filtered_values, &self.options.standstill))
+        }
+    }
+
+    /// Computes the acceleration between each pair of adjacent entries.
+    /// A time-weighted histogram of the accelerations is built from `histogram_edges` in m/s²,
+    /// [DEFAULT_ACCELERATION_HISTOGRAM_EDGES](acceleration::DEFAULT_ACCELERATION_HISTOGRAM_EDGES) can be used as a default.
+    /// All periods with a deceleration above `hard_braking_threshold` in m/s² are reported as hard braking.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] contains less than two [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn acceleration_profile(&self, histogram_edges: &[f32], hard_braking_threshold: f32) -> Result<AccelerationProfile, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.len() < 2 {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_acceleration_profile(&filtered_values, histogram_edges, hard_braking_threshold))
+        }
+    }
+
+    /// Finds all forced brakings and emergency stops.
+    /// Forced brakings are taken from the [FahrtEventKind::ForcedBraking](crate::fahrt_event::FahrtEventKind::ForcedBraking) entries,
+    /// emergency stops are brakings until standstill with an average deceleration above `deceleration_threshold` in m/s².
+    /// [DEFAULT_EMERGENCY_DECELERATION](emergency_braking::DEFAULT_EMERGENCY_DECELERATION) can be used as a default.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn emergency_brakings(&self, deceleration_threshold: f32) -> Result<Vec<EmergencyBraking>, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            Ok(find_emergency_brakings(&filtered_values, deceleration_threshold))
+        }
+    }
+
+    /// Counts the forced brakings and emergency stops, kinds which do not occur are omitted.
+    /// For more details see [emergency_brakings](ResultAnalyser::emergency_brakings).
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn emergency_braking_counts(&self, deceleration_threshold: f32) -> Result<HashMap<EmergencyBrakingKind, usize>, AnalyseError> {
+        let mut counts = HashMap::new();
+        for emergency_braking in self.emergency_brakings(deceleration_threshold)? {
+            *counts.entry(emergency_braking.kind).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Returns the highest driving speed of the whole route.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn max_speed(&self) -> Result<f32, AnalyseError> {
+        *self.cache.max_speed.get_or_init(|| {
+            let filtered_values = self.filtered_values();
+
+            filtered_values.into_iter()
+                .map(|ResultValue::FahrtEintrag(fahrt_eintrag)| fahrt_eintrag.fahrt_speed)
+                .max_by(|a, b| a.total_cmp(b))
+                .ok_or(AnalyseError::NoEntries)
+        })
+    }
+
+    /// Computes the 50th, 90th and 99th percentile of the driving speed excluding idle times.
+    /// The local average speeds between adjacent entries are weighted by time or by distance.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::ZeroDrivingTime] or [AnalyseError::ZeroDistance] if the train never moved.
+    pub fn speed_percentiles(&self, weighting: SpeedWeighting) -> Result<SpeedPercentiles, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            return Err(AnalyseError::NoEntries);
+        }
+
+        find_speed_percentiles(&weighted_local_speeds(&filtered_values, weighting, &self.options.standstill))
+            .ok_or(match weighting {
+                SpeedWeighting::Time => AnalyseError::ZeroDrivingTime,
+                SpeedWeighting::Distance => AnalyseError::ZeroDistance,
+            })
+    }
+
+    /// Computes the time in s spent in each speed band excluding idle times.
+    /// The bands are built from `histogram_edges` in m/s,
+    /// [DEFAULT_SPEED_HISTOGRAM_EDGES](speed_distribution::DEFAULT_SPEED_HISTOGRAM_EDGES) can be used as a default.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    pub fn speed_histogram(&self, histogram_edges: &[f32]) -> Result<Histogram, AnalyseError> {
+        let filtered_values = self.filtered_values();
+
+        if filtered_values.is_empty() {
+            return Err(AnalyseError::NoEntries);
+        }
+
+        let mut histogram = Histogram::new(histogram_edges.to_vec());
+        for (speed, driving_time) in weighted_local_speeds(&filtered_values, SpeedWeighting::Time, &self.options.standstill) {
+            histogram.add(speed, driving_time);
+        }
+        Ok(histogram)
+    }
+
+    /// Splits the route into sections between consecutive stations and computes the main metrics for each of them.
+    /// Stations are all [FahrtEventKind::Station](crate::fahrt_event::FahrtEventKind::Station) entries with a `FahrtText`.
+    /// For more details see [Section].
+    ///
+    /// Sections without valid [FahrtEintrag](ResultValue::FahrtEintrag) entries are skipped, so the other sections of the route are still returned.
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any entries.
+    pub fn sections(&self) -> Result<Vec<Section>, AnalyseError> {
+        let result = self.result.as_ref();
+
+        if result.value.is_empty() {
+            Err(AnalyseError::NoEntries)
+        } else {
+            find_sections(result, &self.options)
+        }
+    }
 }
 
 impl<R: AsRef<ZusiResult>> AsRef<ResultAnalyser<R>> for ResultAnalyser<R> {
@@ -157,14 +715,56 @@ impl<R: AsRef<ZusiResult>> AsRef<ResultAnalyser<R>> for ResultAnalyser<R> {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, Default)]
 pub enum PureAverageSpeedAlgorithm {
+    /// See [pure_average_speed_by_pure_driving_time](ResultAnalyser::pure_average_speed_by_pure_driving_time).
+    #[default]
     PureDrivingTime,
+    /// See [pure_average_speed_by_weighted_local_speeds](ResultAnalyser::pure_average_speed_by_weighted_local_speeds).
     WeightedLocalSpeeds,
+    /// See [pure_average_speed_by_harmonic_local_speeds](ResultAnalyser::pure_average_speed_by_harmonic_local_speeds).
+    HarmonicLocalSpeeds,
+    /// See [pure_average_speed_by_median_local_speed](ResultAnalyser::pure_average_speed_by_median_local_speed).
+    MedianLocalSpeed,
+}
+
+impl PureAverageSpeedAlgorithm {
+    pub const ALL: [PureAverageSpeedAlgorithm; 4] = [
+        Self::PureDrivingTime,
+        Self::WeightedLocalSpeeds,
+        Self::HarmonicLocalSpeeds,
+        Self::MedianLocalSpeed,
+    ];
+}
+
+/// The results of all [PureAverageSpeedAlgorithm] variants in the order of [PureAverageSpeedAlgorithm::ALL].
+#[derive(PartialEq, Debug, Clone)]
+pub struct PureAverageSpeeds {
+    pub values: Vec<(PureAverageSpeedAlgorithm, Result<f32, AnalyseError>)>,
 }
 
-impl Default for PureAverageSpeedAlgorithm {
-    fn default() -> Self {
-        Self::PureDrivingTime
+impl PureAverageSpeeds {
+    pub fn get(&self, algorithm: PureAverageSpeedAlgorithm) -> Option<Result<f32, AnalyseError>> {
+        self.values.iter()
+            .find(|(value_algorithm, _)| *value_algorithm == algorithm)
+            .map(|(_, value)| *value)
     }
+
+    /// Returns the difference in m/s between the highest and the lowest successful result
+    /// or [None] if no algorithm succeeded.
+    pub fn spread(&self) -> Option<f32> {
+        let speeds = self.values.iter().filter_map(|(_, value)| value.ok());
+        let min = speeds.clone().min_by(|a, b| a.total_cmp(b))?;
+        let max = speeds.max_by(|a, b| a.total_cmp(b))?;
+        Some(max - min)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, Default)]
+pub enum DrivingTimeAlgorithm {
+    /// Uses the time between the first and the last entry.
+    #[default]
+    FirstToLastEntry,
+    /// Omits all periods between two entries which are longer than the given duration.
+    ExcludingGaps(Duration),
 }
@@ -0,0 +1,97 @@
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::result_analyser::histogram::Histogram;
+
+/// Default bin edges in m/s² for [AccelerationProfile::histogram].
+pub const DEFAULT_ACCELERATION_HISTOGRAM_EDGES: [f32; 9] = [-1., -0.75, -0.5, -0.25, 0., 0.25, 0.5, 0.75, 1.];
+
+/// A continuous period in which the train decelerated harder than a given threshold.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BrakingEpisode {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub start_km: f32,
+    pub end_km: f32,
+    /// Driving speed at the beginning of the episode in m/s.
+    pub start_speed: f32,
+    /// Driving speed at the end of the episode in m/s.
+    pub end_speed: f32,
+    /// Highest deceleration during the episode in m/s².
+    pub max_deceleration: f32,
+}
+
+/// Acceleration behaviour of a whole run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct AccelerationProfile {
+    /// Highest acceleration in m/s².
+    pub max_acceleration: f32,
+    /// Highest deceleration in m/s² as positive value.
+    pub max_deceleration: f32,
+    /// Time in s spent at each acceleration in m/s². Decelerations are negative.
+    pub histogram: Histogram,
+    pub hard_braking_episodes: Vec<BrakingEpisode>,
+}
+
+pub(crate) fn find_acceleration_profile(
+    values: &[&ResultValue],
+    histogram_edges: &[f32],
+    hard_braking_threshold: f32,
+) -> AccelerationProfile {
+    let mut max_acceleration: f32 = 0.;
+    let mut max_deceleration: f32 = 0.;
+    let mut histogram = Histogram::new(histogram_edges.to_vec());
+    let mut hard_braking_episodes: Vec<BrakingEpisode> = vec![];
+    let mut current: Option<BrakingEpisode> = None;
+
+    for pair in values.windows(2) {
+        let ResultValue::FahrtEintrag(previous) = pair[0];
+        let ResultValue::FahrtEintrag(next) = pair[1];
+
+        // Zusi may record several entries with the same timestamp.
+        let local_driving_time = (next.fahrt_zeit - previous.fahrt_zeit).as_seconds_f32();
+        if local_driving_time <= 0. {
+            continue;
+        }
+
+        let acceleration = (next.fahrt_speed - previous.fahrt_speed) / local_driving_time;
+        max_acceleration = max_acceleration.max(acceleration);
+        max_deceleration = max_deceleration.max(-acceleration);
+        histogram.add(acceleration, local_driving_time);
+
+        if -acceleration > hard_braking_threshold {
+            match current.as_mut() {
+                Some(episode) => {
+                    episode.end_time = next.fahrt_zeit;
+                    episode.end_km = next.fahrt_km;
+                    episode.end_speed = next.fahrt_speed;
+                    episode.max_deceleration = episode.max_deceleration.max(-acceleration);
+                }
+                None => {
+                    current = Some(BrakingEpisode {
+                        start_time: previous.fahrt_zeit,
+                        end_time: next.fahrt_zeit,
+                        start_km: previous.fahrt_km,
+                        end_km: next.fahrt_km,
+                        start_speed: previous.fahrt_speed,
+                        end_speed: next.fahrt_speed,
+                        max_deceleration: -acceleration,
+                    });
+                }
+            }
+        } else if let Some(episode) = current.take() {
+            hard_braking_episodes.push(episode);
+        }
+    }
+
+    if let Some(episode) = current {
+        hard_braking_episodes.push(episode);
+    }
+
+    AccelerationProfile {
+        max_acceleration,
+        max_deceleration,
+        histogram,
+        hard_braking_episodes,
+    }
+}
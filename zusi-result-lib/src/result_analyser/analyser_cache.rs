@@ -0,0 +1,41 @@
+use std::sync::OnceLock;
+use time::Duration;
+
+use crate::result_analyser::AnalyseError;
+
+#[derive(PartialEq, Debug)]
+pub struct AnalyserCache {
+    pub filtered_indices: OnceLock<Vec<usize>>,
+    pub distance: OnceLock<Result<f32, AnalyseError>>,
+    pub average_speed: OnceLock<Result<f32, AnalyseError>>,
+    pub pure_average_speed_by_pure_driving_time: OnceLock<Result<f32, AnalyseError>>,
+    pub pure_average_speed_by_weighted_local_speeds: OnceLock<Result<f32, AnalyseError>>,
+    pub pure_average_speed_by_harmonic_local_speeds: OnceLock<Result<f32, AnalyseError>>,
+    pub pure_average_speed_by_median_local_speed: OnceLock<Result<f32, AnalyseError>>,
+    pub driving_time: OnceLock<Result<Duration, AnalyseError>>,
+    pub pure_driving_time: OnceLock<Result<Duration, AnalyseError>>,
+    pub minimum_run_time: OnceLock<Result<Duration, AnalyseError>>,
+    pub energy_consumption_per_distance: OnceLock<Result<f32, AnalyseError>>,
+    pub energy_consumption_per_pure_driving_time: OnceLock<Result<f32, AnalyseError>>,
+    pub max_speed: OnceLock<Result<f32, AnalyseError>>,
+}
+
+impl AnalyserCache {
+    pub fn new() -> AnalyserCache {
+        Self {
+            filtered_indices: OnceLock::new(),
+            distance: OnceLock::new(),
+            average_speed: OnceLock::new(),
+            pure_average_speed_by_pure_driving_time: OnceLock::new(),
+            pure_average_speed_by_weighted_local_speeds: OnceLock::new(),
+            pure_average_speed_by_harmonic_local_speeds: OnceLock::new(),
+            pure_average_speed_by_median_local_speed: OnceLock::new(),
+            driving_time: OnceLock::new(),
+            pure_driving_time: OnceLock::new(),
+            minimum_run_time: OnceLock::new(),
+            energy_consumption_per_distance: OnceLock::new(),
+            energy_consumption_per_pure_driving_time: OnceLock::new(),
+            max_speed: OnceLock::new(),
+        }
+    }
+}
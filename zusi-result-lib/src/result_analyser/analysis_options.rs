@@ -0,0 +1,100 @@
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::result_analyser::minimum_run_time::MinimumRunTimeOptions;
+
+/// Decides which [FahrtEintrag] entries are used for computing metrics based on positions and speeds.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ValidityPolicy {
+    /// Values of `FahrtWeg` which mark an entry without position.
+    pub invalid_fahrt_weg: Vec<f32>,
+    /// Values of `Fahrtsp` which mark an entry without driving speed.
+    pub invalid_fahrt_speed: Vec<f32>,
+    /// Zusi omits `Fahrtsp` if the train is standing, so a missing value is read as `0`.
+    /// If disabled, all entries with a driving speed of `0` are considered invalid instead.
+    /// As a missing value can't be told apart from a recorded `0`, this also drops all entries of a standing train,
+    /// so standstills are not visible to any metric anymore.
+    pub missing_fahrt_speed_is_zero: bool,
+}
+
+impl ValidityPolicy {
+    pub fn is_valid(&self, fahrt_eintrag: &FahrtEintrag) -> bool {
+        !self.invalid_fahrt_weg.contains(&fahrt_eintrag.fahrt_weg)
+            && !self.invalid_fahrt_speed.contains(&fahrt_eintrag.fahrt_speed)
+            && (self.missing_fahrt_speed_is_zero || fahrt_eintrag.fahrt_speed != 0.)
+    }
+}
+
+impl Default for ValidityPolicy {
+    fn default() -> Self {
+        Self {
+            invalid_fahrt_weg: vec![-1.],
+            invalid_fahrt_speed: vec![-1.],
+            missing_fahrt_speed_is_zero: true,
+        }
+    }
+}
+
+/// Decides when the train is considered standing for computing idle times.
+/// The default values consider every positive driving speed as driving and every standstill as idle time.
+#[derive(PartialEq, Debug, Copy, Clone, Default)]
+pub struct StandstillOptions {
+    /// Highest driving speed in m/s which is still considered as standing.
+    pub speed_threshold: f32,
+    /// Standstills between two driving periods which are shorter than this are considered as driving.
+    pub min_duration: Duration,
+}
+
+/// Options for a [ResultAnalyser](crate::result_analyser::ResultAnalyser).
+#[derive(PartialEq, Debug, Clone)]
+pub struct AnalysisOptions {
+    pub validity: ValidityPolicy,
+    pub standstill: StandstillOptions,
+    pub minimum_run_time: MinimumRunTimeOptions,
+    /// Whether [driving_time](crate::result_analyser::ResultAnalyser::driving_time) only uses entries
+    /// which are valid according to [validity](AnalysisOptions::validity) instead of the first and last entry.
+    /// Enabled by default, as entries without position like a leading `FahrtTyp` `3` entry may carry unrelated times.
+    pub filter_driving_time: bool,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            validity: ValidityPolicy::default(),
+            standstill: StandstillOptions::default(),
+            minimum_run_time: MinimumRunTimeOptions::default(),
+            filter_driving_time: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_validity_policy() {
+        let standing = FahrtEintrag::builder()
+            .fahrt_weg(10.)
+            .fahrt_zeit(datetime!(2019-01-01 23:18))
+            .build();
+        let without_position = FahrtEintrag::builder()
+            .fahrt_weg(-1.)
+            .fahrt_zeit(datetime!(2019-01-01 23:18))
+            .fahrt_speed(5.)
+            .build();
+
+        let policy = ValidityPolicy::default();
+        assert!(policy.is_valid(&standing));
+        assert!(!policy.is_valid(&without_position));
+
+        let policy = ValidityPolicy {
+            invalid_fahrt_weg: vec![],
+            invalid_fahrt_speed: vec![-1.],
+            missing_fahrt_speed_is_zero: false,
+        };
+        assert!(!policy.is_valid(&standing));
+        assert!(policy.is_valid(&without_position));
+    }
+}
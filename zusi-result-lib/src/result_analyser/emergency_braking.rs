@@ -0,0 +1,113 @@
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fahrt_event::FahrtEventKind;
+
+/// Default average deceleration in m/s² above which a braking until standstill is considered an emergency stop,
+/// which is above the usual service braking of passenger trains.
+pub const DEFAULT_EMERGENCY_DECELERATION: f32 = 1.;
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum EmergencyBrakingKind {
+    /// Forced braking by the train protection system, recorded as [FahrtEventKind::ForcedBraking].
+    ForcedBraking,
+    /// Braking until standstill with an average deceleration above the given threshold without a recorded forced braking.
+    /// The average is taken over the continuous deceleration before the standstill,
+    /// as single intervals are too short to be reliable.
+    EmergencyStop,
+}
+
+/// A forced braking or an emergency stop.
+#[derive(PartialEq, Debug, Clone)]
+pub struct EmergencyBraking {
+    pub kind: EmergencyBrakingKind,
+    /// Beginning of the continuous deceleration which led to the event.
+    pub onset_time: PrimitiveDateTime,
+    pub onset_km: f32,
+    /// Driving speed at the onset in m/s.
+    pub onset_speed: f32,
+    /// [None] if the train did not come to a standstill.
+    pub stop_time: Option<PrimitiveDateTime>,
+    /// Distance from the onset to the standstill in m.
+    /// [None] if the train did not come to a standstill.
+    pub stopping_distance: Option<f32>,
+}
+
+fn speed(values: &[&ResultValue], index: usize) -> f32 {
+    let ResultValue::FahrtEintrag(fahrt_eintrag) = values[index];
+    fahrt_eintrag.fahrt_speed
+}
+
+/// Returns the index of the first entry of the continuous deceleration ending at `index`.
+/// Zusi may record a forced braking after the train has already stopped, so a preceding standstill is skipped.
+fn find_onset(values: &[&ResultValue], index: usize) -> usize {
+    let mut onset = index;
+    while onset > 0 && speed(values, onset - 1) <= 0. && speed(values, onset) <= 0. {
+        onset -= 1;
+    }
+    while onset > 0 && speed(values, onset - 1) > speed(values, onset) {
+        onset -= 1;
+    }
+    onset
+}
+
+/// Returns the index of the first standstill if the train decelerates continuously from `index` until standstill.
+fn find_stop(values: &[&ResultValue], index: usize) -> Option<usize> {
+    let mut stop = index;
+    while speed(values, stop) > 0. {
+        if stop + 1 >= values.len() || speed(values, stop + 1) >= speed(values, stop) {
+            return None;
+        }
+        stop += 1;
+    }
+    Some(stop)
+}
+
+fn emergency_braking(values: &[&ResultValue], kind: EmergencyBrakingKind, onset: usize, stop: Option<usize>) -> EmergencyBraking {
+    let ResultValue::FahrtEintrag(first) = values[onset];
+    let stop = stop.map(|stop| {
+        let ResultValue::FahrtEintrag(stop) = values[stop];
+        stop
+    });
+    EmergencyBraking {
+        kind,
+        onset_time: first.fahrt_zeit,
+        onset_km: first.fahrt_km,
+        onset_speed: first.fahrt_speed,
+        stop_time: stop.map(|stop| stop.fahrt_zeit),
+        stopping_distance: stop.map(|stop| stop.fahrt_weg - first.fahrt_weg),
+    }
+}
+
+pub(crate) fn find_emergency_brakings(values: &[&ResultValue], deceleration_threshold: f32) -> Vec<EmergencyBraking> {
+    let mut emergency_brakings: Vec<(usize, EmergencyBraking)> = vec![];
+
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in values.iter().enumerate() {
+        if FahrtEventKind::from(fahrt_eintrag.fahrt_typ) == FahrtEventKind::ForcedBraking {
+            let onset = find_onset(values, index);
+            let stop = find_stop(values, onset);
+            emergency_brakings.push((onset, emergency_braking(values, EmergencyBrakingKind::ForcedBraking, onset, stop)));
+        }
+    }
+
+    for stop in 1..values.len() {
+        if speed(values, stop) > 0. || speed(values, stop - 1) <= 0. {
+            continue;
+        }
+        let onset = find_onset(values, stop);
+        // the deceleration belongs to a forced braking
+        if emergency_brakings.iter().any(|(forced_braking_onset, _)| *forced_braking_onset == onset) {
+            continue;
+        }
+
+        let ResultValue::FahrtEintrag(first) = values[onset];
+        let ResultValue::FahrtEintrag(last) = values[stop];
+        let braking_time = (last.fahrt_zeit - first.fahrt_zeit).as_seconds_f32();
+        if braking_time > 0. && first.fahrt_speed / braking_time > deceleration_threshold {
+            emergency_brakings.push((onset, emergency_braking(values, EmergencyBrakingKind::EmergencyStop, onset, Some(stop))));
+        }
+    }
+
+    emergency_brakings.sort_by_key(|(onset, _)| *onset);
+    emergency_brakings.into_iter().map(|(_, emergency_braking)| emergency_braking).collect()
+}
@@ -1,14 +1,86 @@
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
 
-pub fn zusi_result_to_ptr_vec(result: &ZusiResult) -> Vec<&ResultValue> {
-    result.value.iter().map(|result_value| result_value).collect()
+use crate::result_analyser::analysis_options::{StandstillOptions, ValidityPolicy};
+
+/// Returns the indices of all entries which are valid according to `policy`.
+pub fn filter_valid_indices(result: &ZusiResult, policy: &ValidityPolicy) -> Vec<usize> {
+    result.value.iter().enumerate().filter(
+        |(_, ResultValue::FahrtEintrag(fahrt_eintrag))| policy.is_valid(fahrt_eintrag)
+    ).map(|(index, _)| index).collect()
+}
+
+/// Decides for each pair of adjacent entries whether the train is driving in between.
+/// The result contains one value less than `values`.
+pub fn find_driving_intervals(values: &[&ResultValue], standstill: &StandstillOptions) -> Vec<bool> {
+    let mut driving_intervals: Vec<bool> = values.windows(2)
+        .map(|pair| {
+            let ResultValue::FahrtEintrag(current) = pair[0];
+            let ResultValue::FahrtEintrag(next) = pair[1];
+            current.fahrt_speed > standstill.speed_threshold || next.fahrt_speed > standstill.speed_threshold
+        })
+        .collect();
+
+    // count short standstills between two driving periods as driving
+    let mut start = 0;
+    while start < driving_intervals.len() {
+        if driving_intervals[start] {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end + 1 < driving_intervals.len() && !driving_intervals[end + 1] {
+            end += 1;
+        }
+        let ResultValue::FahrtEintrag(first) = values[start];
+        let ResultValue::FahrtEintrag(last) = values[end + 1];
+        if start > 0 && end + 1 < driving_intervals.len() && last.fahrt_zeit - first.fahrt_zeit < standstill.min_duration {
+            driving_intervals[start..=end].fill(true);
+        }
+        start = end + 1;
+    }
+    driving_intervals
 }
 
-pub fn filter_valid_fahrt_weg_and_fahrt_speed(result: &ZusiResult) -> Vec<&ResultValue> {
-    zusi_result_to_ptr_vec(result).into_iter().filter(
-        |ResultValue::FahrtEintrag(fahrt_eintrag)|
-            fahrt_eintrag.fahrt_weg != -1. && fahrt_eintrag.fahrt_speed != -1.
-    ).collect()
+/// Returns the indices of the first and the last entry of the standstill which contains the entry at `index`.
+/// A standstill consists of adjacent intervals without driving according to `driving_intervals`,
+/// which are computed by [find_driving_intervals], so it follows the same [StandstillOptions] as
+/// [pure_driving_time](crate::result_analyser::ResultAnalyser::pure_driving_time).
+///
+/// Returns [None] if the entry is not part of a standstill.
+pub fn find_standstill(driving_intervals: &[bool], index: usize) -> Option<(usize, usize)> {
+    let is_standing = |interval: usize| !driving_intervals[interval];
+
+    let standing_before = index > 0 && is_standing(index - 1);
+    let standing_after = index < driving_intervals.len() && is_standing(index);
+    if !standing_before && !standing_after {
+        return None;
+    }
+
+    let mut start = index;
+    while start > 0 && is_standing(start - 1) {
+        start -= 1;
+    }
+    let mut end = index;
+    while end < driving_intervals.len() && is_standing(end) {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+/// Returns the indices of the first and the last entry of all standstills, see [find_standstill].
+pub fn find_standstills(driving_intervals: &[bool]) -> Vec<(usize, usize)> {
+    let mut standstills = vec![];
+    let mut index = 0;
+    while index <= driving_intervals.len() {
+        match find_standstill(driving_intervals, index) {
+            Some((start, end)) => {
+                standstills.push((start, end));
+                index = end + 1;
+            }
+            None => index += 1,
+        }
+    }
+    standstills
 }
 
 #[cfg(test)]
@@ -18,7 +90,7 @@ mod tests {
     use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
 
     #[test]
-    fn test_filter_valid_fahrt_weg_and_fahrt_speed() {
+    fn test_filter_valid_indices() {
         let result = ZusiResult::builder()
             .datum(datetime!(2019-01-01 23:14))
             .value(vec![
@@ -47,14 +119,85 @@ mod tests {
             ])
             .build();
 
-        let expected_filtered_result_values = vec![
-            &result.value[1],
-            &result.value[3],
-        ];
-
         assert_eq!(
-            filter_valid_fahrt_weg_and_fahrt_speed(&result),
-            expected_filtered_result_values,
+            filter_valid_indices(&result, &ValidityPolicy::default()),
+            vec![1, 3],
         )
     }
+
+    #[test]
+    fn test_find_driving_intervals() {
+        let result = ZusiResult::builder()
+            .datum(datetime!(2019-01-01 23:14))
+            .value([(0, 0.), (10, 0.), (20, 5.), (30, 0.), (40, 0.), (50, 0.01), (60, 5.), (70, 0.), (100, 0.)].into_iter()
+                .map(|(second, fahrt_speed)| ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:18) + time::Duration::seconds(second))
+                    .fahrt_speed(fahrt_speed)
+                    .build()))
+                .collect())
+            .build();
+        let values: Vec<&ResultValue> = result.value.iter().collect();
+
+        assert_eq!(
+            find_driving_intervals(&values, &StandstillOptions::default()),
+            vec![false, true, true, false, true, true, true, false],
+        );
+        assert_eq!(
+            find_driving_intervals(&values, &StandstillOptions {
+                speed_threshold: 0.1,
+                min_duration: time::Duration::ZERO,
+            }),
+            vec![false, true, true, false, false, true, true, false],
+        );
+        assert_eq!(
+            find_driving_intervals(&values, &StandstillOptions {
+                speed_threshold: 0.1,
+                min_duration: time::Duration::seconds(30),
+            }),
+            vec![false, true, true, true, true, true, true, false],
+        );
+    }
+
+    #[test]
+    fn test_find_standstill() {
+        let result = ZusiResult::builder()
+            .datum(datetime!(2019-01-01 23:14))
+            .value(vec![
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:18))
+                    .fahrt_speed(3.)
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:19))
+                    .fahrt_speed(0.)
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:20))
+                    .fahrt_speed(0.)
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:21))
+                    .fahrt_speed(0.)
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_zeit(datetime!(2019-01-01 23:22))
+                    .fahrt_speed(2.)
+                    .build()),
+            ])
+            .build();
+        let values: Vec<&ResultValue> = result.value.iter().collect();
+        let driving_intervals = find_driving_intervals(&values, &StandstillOptions::default());
+
+        assert_eq!(find_standstill(&driving_intervals, 0), None);
+        assert_eq!(find_standstill(&driving_intervals, 2), Some((1, 3)));
+        assert_eq!(find_standstill(&driving_intervals, 3), Some((1, 3)));
+        assert_eq!(find_standstills(&driving_intervals), vec![(1, 3)]);
+
+        let driving_intervals = find_driving_intervals(&values, &StandstillOptions {
+            speed_threshold: 2.,
+            min_duration: time::Duration::ZERO,
+        });
+        assert_eq!(find_standstill(&driving_intervals, 4), Some((1, 4)));
+        assert_eq!(find_standstills(&driving_intervals), vec![(1, 4)]);
+    }
 }
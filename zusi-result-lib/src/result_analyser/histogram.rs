@@ -0,0 +1,81 @@
+/// Histogram with weighted values.
+///
+/// The bins are defined by ascending `edges`. There is one open-ended bin below the first edge,
+/// one bin between each pair of adjacent edges and one open-ended bin above the last edge,
+/// so there are always `edges.len() + 1` bins.
+/// A value equal to an edge belongs to the bin above the edge.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Histogram {
+    pub edges: Vec<f32>,
+    pub weights: Vec<f32>,
+}
+
+impl Histogram {
+    pub fn new(edges: Vec<f32>) -> Histogram {
+        let weights = vec![0.; edges.len() + 1];
+        Self {
+            edges,
+            weights,
+        }
+    }
+
+    /// Returns the index of the bin which contains `value`.
+    pub fn bin(&self, value: f32) -> usize {
+        self.edges.partition_point(|edge| *edge <= value)
+    }
+
+    pub fn add(&mut self, value: f32, weight: f32) {
+        let bin = self.bin(value);
+        self.weights[bin] += weight;
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.weights.iter().sum()
+    }
+
+    /// Adds the weights of `other` to this histogram.
+    ///
+    /// Panics if both histograms do not have the same edges.
+    pub fn merge(&mut self, other: &Histogram) {
+        assert_eq!(self.edges, other.edges, "histograms with different edges can't be merged");
+        for (weight, other_weight) in self.weights.iter_mut().zip(other.weights.iter()) {
+            *weight += other_weight;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram() {
+        let mut histogram = Histogram::new(vec![-1., 0., 1.]);
+        histogram.add(-2., 1.);
+        histogram.add(-1., 2.);
+        histogram.add(0.5, 3.);
+        histogram.add(1., 4.);
+        histogram.add(5., 5.);
+
+        assert_eq!(histogram.weights, vec![1., 2., 3., 9.]);
+        assert_eq!(histogram.total_weight(), 15.);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut histogram = Histogram::new(vec![0.]);
+        histogram.add(-1., 1.);
+        let mut other = Histogram::new(vec![0.]);
+        other.add(-1., 2.);
+        other.add(1., 3.);
+
+        histogram.merge(&other);
+        assert_eq!(histogram.weights, vec![3., 3.]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_different_edges() {
+        Histogram::new(vec![0.]).merge(&Histogram::new(vec![1.]));
+    }
+}
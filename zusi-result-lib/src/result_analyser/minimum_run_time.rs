@@ -0,0 +1,109 @@
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::{find_driving_intervals, find_standstill};
+use crate::result_analyser::speed_violation::permitted_speed;
+
+/// Driving dynamics of the ideal train for [minimum_run_time](crate::result_analyser::ResultAnalyser::minimum_run_time).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct MinimumRunTimeOptions {
+    /// Acceleration in m/s².
+    pub acceleration: f32,
+    /// Deceleration in m/s² as positive value.
+    pub deceleration: f32,
+}
+
+impl Default for MinimumRunTimeOptions {
+    fn default() -> Self {
+        Self {
+            acceleration: 0.5,
+            deceleration: 0.5,
+        }
+    }
+}
+
+/// Returns the time needed for a segment of `distance` which is entered at `entry_speed` and left at `exit_speed`
+/// by accelerating as far as possible up to `max_speed` and braking as late as possible.
+fn segment_time(distance: f32, entry_speed: f32, exit_speed: f32, max_speed: f32, options: &MinimumRunTimeOptions) -> f32 {
+    // like in pure_driving_time, intervals without driving speed are omitted
+    if distance <= 0. || max_speed <= 0. {
+        return 0.;
+    }
+    let (acceleration, deceleration) = (options.acceleration, options.deceleration);
+
+    let peak_speed = ((2. * acceleration * deceleration * distance
+        + deceleration * entry_speed.powi(2)
+        + acceleration * exit_speed.powi(2))
+        / (acceleration + deceleration)).sqrt();
+
+    if peak_speed <= max_speed {
+        (peak_speed - entry_speed) / acceleration + (peak_speed - exit_speed) / deceleration
+    } else {
+        let acceleration_distance = (max_speed.powi(2) - entry_speed.powi(2)) / (2. * acceleration);
+        let deceleration_distance = (max_speed.powi(2) - exit_speed.powi(2)) / (2. * deceleration);
+        let cruising_distance = distance - acceleration_distance - deceleration_distance;
+        (max_speed - entry_speed) / acceleration + (max_speed - exit_speed) / deceleration + cruising_distance / max_speed
+    }
+}
+
+/// Computes the running time of an ideal train over the same positions as the recorded run.
+///
+/// Between two entries, the ideal train may drive at the permitted speed of the first entry,
+/// so it never profits from overspeeding of the recorded train.
+/// Only if no limit or a limit of zero is recorded, it may drive at the recorded driving speed instead, so these limits do not stop the train.
+/// It stands still wherever the recorded train did according to the [StandstillOptions],
+/// and starts and ends with the recorded driving speed.
+/// The speed profile is built by a forward pass limiting the acceleration and a backward pass limiting the deceleration.
+pub(crate) fn find_minimum_run_time(values: &[&ResultValue], options: &MinimumRunTimeOptions, standstill: &StandstillOptions) -> Duration {
+    if values.len() < 2 {
+        return Duration::ZERO;
+    }
+
+    let driving_intervals = find_driving_intervals(values, standstill);
+    let segments: Vec<(f32, f32)> = values.windows(2)
+        .zip(driving_intervals.iter())
+        .map(|(pair, driving)| {
+            let ResultValue::FahrtEintrag(current) = pair[0];
+            let ResultValue::FahrtEintrag(next) = pair[1];
+            if !driving {
+                return (0., 0.);
+            }
+            let max_speed = permitted_speed(current)
+                .map(|(_, limit_speed)| limit_speed)
+                .filter(|limit_speed| *limit_speed > 0.)
+                .unwrap_or_else(|| current.fahrt_speed.max(next.fahrt_speed));
+            ((next.fahrt_weg - current.fahrt_weg).max(0.), max_speed)
+        })
+        .collect();
+
+    let mut speeds: Vec<f32> = values.iter().enumerate()
+        .map(|(index, ResultValue::FahrtEintrag(fahrt_eintrag))| {
+            if find_standstill(&driving_intervals, index).is_some() {
+                return 0.;
+            }
+            let before = index.checked_sub(1).map_or(f32::INFINITY, |before| segments[before].1);
+            let after = segments.get(index).map_or(f32::INFINITY, |(_, max_speed)| *max_speed);
+            let max_speed = before.min(after);
+            if index == 0 || index == values.len() - 1 {
+                max_speed.min(fahrt_eintrag.fahrt_speed)
+            } else {
+                max_speed
+            }
+        })
+        .collect();
+
+    for (index, (distance, _)) in segments.iter().enumerate() {
+        let reachable_speed = (speeds[index].powi(2) + 2. * options.acceleration * distance).sqrt();
+        speeds[index + 1] = speeds[index + 1].min(reachable_speed);
+    }
+    for (index, (distance, _)) in segments.iter().enumerate().rev() {
+        let brakeable_speed = (speeds[index + 1].powi(2) + 2. * options.deceleration * distance).sqrt();
+        speeds[index] = speeds[index].min(brakeable_speed);
+    }
+
+    let seconds: f32 = segments.iter().enumerate()
+        .map(|(index, (distance, max_speed))| segment_time(*distance, speeds[index], speeds[index + 1], *max_speed, options))
+        .sum();
+    Duration::seconds_f32(seconds)
+}
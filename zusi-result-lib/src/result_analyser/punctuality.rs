@@ -0,0 +1,103 @@
+use time::macros::datetime;
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fahrt_event::FahrtEventKind;
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::{find_driving_intervals, find_standstill};
+
+/// Punctuality at a single station of the timetable.
+#[derive(PartialEq, Debug, Clone)]
+pub struct StationPunctuality {
+    pub name: String,
+    pub scheduled_arrival: Option<PrimitiveDateTime>,
+    pub scheduled_departure: Option<PrimitiveDateTime>,
+    /// Beginning of the standstill at the station or the time of passing it.
+    pub actual_arrival: PrimitiveDateTime,
+    /// End of the standstill at the station or the time of passing it.
+    pub actual_departure: PrimitiveDateTime,
+    /// Negative if the train arrived early.
+    pub arrival_delay: Option<Duration>,
+    /// Negative if the train departed early.
+    pub departure_delay: Option<Duration>,
+}
+
+/// Punctuality of a whole run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Punctuality {
+    pub stations: Vec<StationPunctuality>,
+    /// Highest arrival or departure delay of all stations.
+    pub max_delay: Option<Duration>,
+    /// Arrival delay at the last station with a scheduled arrival.
+    pub final_delay: Option<Duration>,
+}
+
+/// Converts a spreadsheet-style day serial as used by `FahrtFplAnk` and `FahrtFplAbf` into a timestamp.
+/// The integer part counts the days since 1899-12-30 while the fractional part is the time of day.
+///
+/// Zusi stores these values with single precision,
+/// so the resulting timestamps are only accurate to about five minutes.
+pub fn day_serial_to_datetime(day_serial: f64) -> PrimitiveDateTime {
+    datetime!(1899-12-30 0:00) + Duration::seconds((day_serial * 86400.).round() as i64)
+}
+
+pub(crate) fn find_punctuality(values: &[&ResultValue], standstill: &StandstillOptions) -> Punctuality {
+    let mut stations: Vec<StationPunctuality> = vec![];
+    let driving_intervals = find_driving_intervals(values, standstill);
+
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in values.iter().enumerate() {
+        if FahrtEventKind::from(fahrt_eintrag.fahrt_typ) != FahrtEventKind::Station
+            || fahrt_eintrag.fahrt_text.is_empty()
+            || (fahrt_eintrag.fahrt_fpl_ank.is_none() && fahrt_eintrag.fahrt_fpl_abf.is_none()) {
+            continue;
+        }
+
+        let (arrival_index, departure_index) = find_standstill(&driving_intervals, index).unwrap_or((index, index));
+        let ResultValue::FahrtEintrag(arrival) = values[arrival_index];
+        let ResultValue::FahrtEintrag(departure) = values[departure_index];
+
+        // Zusi sometimes records a station twice during the same standstill.
+        if let Some(previous) = stations.last() {
+            if previous.name == fahrt_eintrag.fahrt_text && previous.actual_arrival == arrival.fahrt_zeit {
+                continue;
+            }
+        }
+
+        let scheduled_arrival = fahrt_eintrag.fahrt_fpl_ank.map(day_serial_to_datetime);
+        let scheduled_departure = fahrt_eintrag.fahrt_fpl_abf.map(day_serial_to_datetime);
+
+        stations.push(StationPunctuality {
+            name: fahrt_eintrag.fahrt_text.clone(),
+            scheduled_arrival,
+            scheduled_departure,
+            actual_arrival: arrival.fahrt_zeit,
+            actual_departure: departure.fahrt_zeit,
+            arrival_delay: scheduled_arrival.map(|scheduled| arrival.fahrt_zeit - scheduled),
+            departure_delay: scheduled_departure.map(|scheduled| departure.fahrt_zeit - scheduled),
+        });
+    }
+
+    let max_delay = stations.iter()
+        .flat_map(|station| [station.arrival_delay, station.departure_delay])
+        .flatten()
+        .max();
+    let final_delay = stations.iter().rev().find_map(|station| station.arrival_delay);
+
+    Punctuality {
+        stations,
+        max_delay,
+        final_delay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_serial_to_datetime() {
+        assert_eq!(day_serial_to_datetime(0.), datetime!(1899-12-30 0:00));
+        assert_eq!(day_serial_to_datetime(43410.3125), datetime!(2018-11-06 7:30));
+        assert_eq!(day_serial_to_datetime(43410.308594), datetime!(2018-11-06 7:24:23));
+    }
+}
@@ -0,0 +1,95 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::fahrt_event::FahrtEventKind;
+use crate::result_analyser::analysis_options::{AnalysisOptions, ValidityPolicy};
+use crate::result_analyser::{AnalyseError, PureAverageSpeedAlgorithm, ResultAnalyser};
+
+/// The part of a run between two consecutive stations.
+///
+/// A section starts with the entry of the first station and ends with the entry of the second station,
+/// so the dwell time at the first station is part of the section.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Section {
+    pub from: String,
+    pub to: String,
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    /// For more details see [distance](ResultAnalyser::distance).
+    pub distance: f32,
+    /// For more details see [driving_time](ResultAnalyser::driving_time).
+    pub driving_time: Duration,
+    /// For more details see [pure_driving_time](ResultAnalyser::pure_driving_time).
+    pub pure_driving_time: Duration,
+    /// [None] if the section has no driving time.
+    /// For more details see [average_speed](ResultAnalyser::average_speed).
+    pub average_speed: Option<f32>,
+    /// [None] if the section has no pure driving time.
+    /// For more details see [pure_average_speed](ResultAnalyser::pure_average_speed).
+    pub pure_average_speed: Option<f32>,
+    /// For more details see [minimum_run_time](ResultAnalyser::minimum_run_time).
+    pub minimum_run_time: Duration,
+    /// For more details see [run_time_reserve](ResultAnalyser::run_time_reserve).
+    pub run_time_reserve: Duration,
+}
+
+/// Returns the indices of all station entries with a name and a position according to the [ValidityPolicy].
+/// Consecutive entries of the same station are only returned once.
+pub(crate) fn find_station_indices(result: &ZusiResult, policy: &ValidityPolicy) -> Vec<usize> {
+    let mut indices: Vec<usize> = vec![];
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in result.value.iter().enumerate() {
+        if FahrtEventKind::from(fahrt_eintrag.fahrt_typ) != FahrtEventKind::Station
+            || fahrt_eintrag.fahrt_text.is_empty()
+            || policy.invalid_fahrt_weg.contains(&fahrt_eintrag.fahrt_weg) {
+            continue;
+        }
+        if let Some(previous) = indices.last() {
+            let ResultValue::FahrtEintrag(previous) = &result.value[*previous];
+            if previous.fahrt_text == fahrt_eintrag.fahrt_text {
+                continue;
+            }
+        }
+        indices.push(index);
+    }
+    indices
+}
+
+/// Splits the [ZusiResult] into sections and analyses each of them with its own [ResultAnalyser].
+/// Sections without valid entries are skipped.
+pub(crate) fn find_sections(result: &ZusiResult, options: &AnalysisOptions) -> Result<Vec<Section>, AnalyseError> {
+    let mut sections: Vec<Section> = vec![];
+    for pair in find_station_indices(result, &options.validity).windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        match analyse_section(result, start, end, options) {
+            Ok(section) => sections.push(section),
+            Err(AnalyseError::NoEntries) => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(sections)
+}
+
+fn analyse_section(result: &ZusiResult, start: usize, end: usize, options: &AnalysisOptions) -> Result<Section, AnalyseError> {
+    let ResultValue::FahrtEintrag(first) = &result.value[start];
+    let ResultValue::FahrtEintrag(last) = &result.value[end];
+
+    let analyser = ResultAnalyser::new_with_options(ZusiResult::builder()
+        .zugnummer(result.zugnummer.clone())
+        .datum(result.datum)
+        .value(result.value[start..=end].to_vec())
+        .build(), options.clone());
+
+    Ok(Section {
+        from: first.fahrt_text.clone(),
+        to: last.fahrt_text.clone(),
+        start_time: first.fahrt_zeit,
+        end_time: last.fahrt_zeit,
+        distance: analyser.distance()?,
+        driving_time: analyser.driving_time()?,
+        pure_driving_time: analyser.pure_driving_time()?,
+        average_speed: analyser.average_speed().ok(),
+        pure_average_speed: analyser.pure_average_speed(PureAverageSpeedAlgorithm::default()).ok(),
+        minimum_run_time: analyser.minimum_run_time()?,
+        run_time_reserve: analyser.run_time_reserve()?,
+    })
+}
@@ -0,0 +1,114 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fahrt_event::FahrtEventKind;
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::{find_driving_intervals, find_standstills};
+
+/// A continuous period in which a signal restricted the permitted speed to the same value.
+///
+/// The restriction lasts from the first entry with the limit until the next entry without it,
+/// or until the last entry of the run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SignalRestriction {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub duration: Duration,
+    /// Distance driven during the restriction in m.
+    pub distance: f32,
+    /// The value of `FahrtspSignal` in m/s.
+    pub limit_speed: f32,
+    /// The `FahrtText` of the last signal passed before the restriction started.
+    /// [None] if no signal with a text has been passed yet.
+    pub signal: Option<String>,
+    /// Time needed during the restriction in addition to driving at `FahrtspStrecke`.
+    /// Standstills at scheduled stops are not counted, as the train would have stopped there anyway,
+    /// while waiting at a signal is counted.
+    pub time_lost: Duration,
+}
+
+/// All signal restrictions of a run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SignalRestrictions {
+    pub restrictions: Vec<SignalRestriction>,
+    /// Sum of the [time_lost](SignalRestriction::time_lost) of all restrictions.
+    pub total_time_lost: Duration,
+}
+
+/// Returns the time needed between two entries in addition to driving at `FahrtspStrecke`.
+/// Intervals without a route speed do not count as time lost.
+fn local_time_lost(time: Duration, distance: f32, route_speed: f32) -> Duration {
+    if route_speed <= 0. {
+        return Duration::ZERO;
+    }
+    let time_at_route_speed = Duration::seconds_f32(distance.max(0.) / route_speed);
+    (time - time_at_route_speed).max(Duration::ZERO)
+}
+
+/// Returns for each pair of adjacent entries whether the train is standing at a scheduled stop in between.
+/// Like in [stops](crate::result_analyser::ResultAnalyser::stops), a standstill is a scheduled stop
+/// if it contains a station entry with a scheduled arrival or departure.
+fn find_scheduled_stop_intervals(values: &[&ResultValue], standstill: &StandstillOptions) -> Vec<bool> {
+    let mut scheduled_stop_intervals = vec![false; values.len().saturating_sub(1)];
+    for (start, end) in find_standstills(&find_driving_intervals(values, standstill)) {
+        let scheduled = values[start..=end].iter().any(|ResultValue::FahrtEintrag(fahrt_eintrag)|
+            FahrtEventKind::from(fahrt_eintrag.fahrt_typ) == FahrtEventKind::Station
+                && (fahrt_eintrag.fahrt_fpl_ank.is_some() || fahrt_eintrag.fahrt_fpl_abf.is_some())
+        );
+        if scheduled {
+            scheduled_stop_intervals[start..end].fill(true);
+        }
+    }
+    scheduled_stop_intervals
+}
+
+pub(crate) fn find_signal_restrictions(values: &[&ResultValue], standstill: &StandstillOptions) -> SignalRestrictions {
+    let scheduled_stop_intervals = find_scheduled_stop_intervals(values, standstill);
+    let mut restrictions: Vec<SignalRestriction> = vec![];
+    let mut current: Option<SignalRestriction> = None;
+    let mut last_signal: Option<&String> = None;
+
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in values.iter().enumerate() {
+        if FahrtEventKind::from(fahrt_eintrag.fahrt_typ) == FahrtEventKind::Signal && !fahrt_eintrag.fahrt_text.is_empty() {
+            last_signal = Some(&fahrt_eintrag.fahrt_text);
+        }
+
+        let limit_speed = fahrt_eintrag.fahrt_speed_signal;
+        if current.as_ref().is_some_and(|restriction| restriction.limit_speed != limit_speed) {
+            restrictions.push(current.take().unwrap());
+        }
+        if limit_speed == -1. {
+            continue;
+        }
+
+        let restriction = current.get_or_insert_with(|| SignalRestriction {
+            start_time: fahrt_eintrag.fahrt_zeit,
+            end_time: fahrt_eintrag.fahrt_zeit,
+            duration: Duration::ZERO,
+            distance: 0.,
+            limit_speed,
+            signal: last_signal.cloned(),
+            time_lost: Duration::ZERO,
+        });
+
+        if let Some(ResultValue::FahrtEintrag(next)) = values.get(index + 1) {
+            let local_time = next.fahrt_zeit - fahrt_eintrag.fahrt_zeit;
+            let local_distance = next.fahrt_weg - fahrt_eintrag.fahrt_weg;
+            restriction.end_time = next.fahrt_zeit;
+            restriction.duration += local_time;
+            restriction.distance += local_distance;
+            if !scheduled_stop_intervals[index] {
+                restriction.time_lost += local_time_lost(local_time, local_distance, fahrt_eintrag.fahrt_speed_strecke);
+            }
+        }
+    }
+
+    if let Some(restriction) = current {
+        restrictions.push(restriction);
+    }
+
+    SignalRestrictions {
+        total_time_lost: restrictions.iter().map(|restriction| restriction.time_lost).sum(),
+        restrictions,
+    }
+}
@@ -0,0 +1,90 @@
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::find_driving_intervals;
+
+/// Default bin edges in m/s for [speed_histogram](crate::result_analyser::ResultAnalyser::speed_histogram),
+/// which are bands of 40 km/h up to 160 km/h.
+pub const DEFAULT_SPEED_HISTOGRAM_EDGES: [f32; 4] = [40. / 3.6, 80. / 3.6, 120. / 3.6, 160. / 3.6];
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SpeedWeighting {
+    /// Weights each speed by the time driven at it.
+    Time,
+    /// Weights each speed by the distance driven at it.
+    Distance,
+}
+
+/// Percentiles of the driving speed in m/s.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct SpeedPercentiles {
+    pub p50: f32,
+    pub p90: f32,
+    pub p99: f32,
+}
+
+/// Returns the local average speed in m/s between each pair of adjacent entries
+/// together with its weight in s or m. Idle times are omitted like in
+/// [pure_driving_time](crate::result_analyser::ResultAnalyser::pure_driving_time).
+pub(crate) fn weighted_local_speeds(values: &[&ResultValue], weighting: SpeedWeighting, standstill: &StandstillOptions) -> Vec<(f32, f32)> {
+    values.windows(2)
+        .zip(find_driving_intervals(values, standstill))
+        .filter_map(|(pair, driving)| {
+            let ResultValue::FahrtEintrag(current) = pair[0];
+            let ResultValue::FahrtEintrag(next) = pair[1];
+            if !driving {
+                return None;
+            }
+            let local_average_speed = (current.fahrt_speed + next.fahrt_speed) / 2.;
+            let weight = match weighting {
+                SpeedWeighting::Time => (next.fahrt_zeit - current.fahrt_zeit).as_seconds_f32(),
+                SpeedWeighting::Distance => next.fahrt_weg - current.fahrt_weg,
+            };
+            Some((local_average_speed, weight))
+        })
+        .filter(|(_, weight)| *weight > 0.)
+        .collect()
+}
+
+/// Returns the smallest value for which the weights of all values up to it
+/// make up at least the fraction `quantile` of the total weight.
+///
+/// Returns [None] if there are no values.
+pub fn weighted_quantile(samples: &[(f32, f32)], quantile: f32) -> Option<f32> {
+    let mut samples = samples.to_vec();
+    samples.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let total_weight: f32 = samples.iter().map(|(_, weight)| weight).sum();
+    let mut cumulative_weight = 0.;
+    for (value, weight) in samples.iter() {
+        cumulative_weight += weight;
+        if cumulative_weight >= quantile * total_weight {
+            return Some(*value);
+        }
+    }
+    samples.last().map(|(value, _)| *value)
+}
+
+pub(crate) fn find_speed_percentiles(samples: &[(f32, f32)]) -> Option<SpeedPercentiles> {
+    Some(SpeedPercentiles {
+        p50: weighted_quantile(samples, 0.5)?,
+        p90: weighted_quantile(samples, 0.9)?,
+        p99: weighted_quantile(samples, 0.99)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_quantile() {
+        let samples = [(30., 1.), (10., 2.), (20., 7.)];
+        assert_eq!(weighted_quantile(&samples, 0.), Some(10.));
+        assert_eq!(weighted_quantile(&samples, 0.2), Some(10.));
+        assert_eq!(weighted_quantile(&samples, 0.5), Some(20.));
+        assert_eq!(weighted_quantile(&samples, 0.9), Some(20.));
+        assert_eq!(weighted_quantile(&samples, 0.99), Some(30.));
+        assert_eq!(weighted_quantile(&[], 0.5), None);
+    }
+}
@@ -0,0 +1,86 @@
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+/// The kinds of permitted speed recorded by each [FahrtEintrag].
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SpeedLimit {
+    /// Permitted speed of the route (`FahrtspStrecke`).
+    Strecke,
+    /// Permitted speed given by a signal (`FahrtspSignal`).
+    Signal,
+    /// Permitted speed supervised by the train protection system (`FahrtspZugsicherung`).
+    Zugsicherung,
+}
+
+/// A continuous period in which the driving speed exceeded the permitted speed.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SpeedViolation {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub start_km: f32,
+    pub end_km: f32,
+    /// Highest difference between driving speed and permitted speed in m/s.
+    pub max_overspeed: f32,
+    /// The limit which has been exceeded at the moment of [max_overspeed](SpeedViolation::max_overspeed).
+    pub limit: SpeedLimit,
+    /// The value of [limit](SpeedViolation::limit) in m/s.
+    pub limit_speed: f32,
+}
+
+/// Returns the most restrictive permitted speed of a [FahrtEintrag].
+/// A value of `-1` means that there is no limit of this kind.
+pub fn permitted_speed(fahrt_eintrag: &FahrtEintrag) -> Option<(SpeedLimit, f32)> {
+    [
+        (SpeedLimit::Strecke, fahrt_eintrag.fahrt_speed_strecke),
+        (SpeedLimit::Signal, fahrt_eintrag.fahrt_speed_signal),
+        (SpeedLimit::Zugsicherung, fahrt_eintrag.fahrt_speed_zugsicherung),
+    ]
+        .into_iter()
+        .filter(|(_, speed)| *speed != -1.)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+pub(crate) fn find_speed_violations(values: &[&ResultValue]) -> Vec<SpeedViolation> {
+    let mut violations: Vec<SpeedViolation> = vec![];
+    let mut current: Option<SpeedViolation> = None;
+
+    for ResultValue::FahrtEintrag(fahrt_eintrag) in values.iter() {
+        let overspeed = permitted_speed(fahrt_eintrag)
+            .map(|(limit, limit_speed)| (limit, limit_speed, fahrt_eintrag.fahrt_speed - limit_speed))
+            .filter(|(_, _, overspeed)| *overspeed > 0.);
+
+        match (overspeed, current.as_mut()) {
+            (Some((limit, limit_speed, overspeed)), Some(violation)) => {
+                violation.end_time = fahrt_eintrag.fahrt_zeit;
+                violation.end_km = fahrt_eintrag.fahrt_km;
+                if overspeed > violation.max_overspeed {
+                    violation.max_overspeed = overspeed;
+                    violation.limit = limit;
+                    violation.limit_speed = limit_speed;
+                }
+            }
+            (Some((limit, limit_speed, overspeed)), None) => {
+                current = Some(SpeedViolation {
+                    start_time: fahrt_eintrag.fahrt_zeit,
+                    end_time: fahrt_eintrag.fahrt_zeit,
+                    start_km: fahrt_eintrag.fahrt_km,
+                    end_km: fahrt_eintrag.fahrt_km,
+                    max_overspeed: overspeed,
+                    limit,
+                    limit_speed,
+                });
+            }
+            (None, _) => {
+                if let Some(violation) = current.take() {
+                    violations.push(violation);
+                }
+            }
+        }
+    }
+
+    if let Some(violation) = current {
+        violations.push(violation);
+    }
+    violations
+}
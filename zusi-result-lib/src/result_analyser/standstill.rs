@@ -0,0 +1,62 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fahrt_event::FahrtEventKind;
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::{find_driving_intervals, find_standstills};
+
+/// The entry with a `FahrtText` which is closest to a [StandstillPeriod].
+#[derive(PartialEq, Debug, Clone)]
+pub struct NearestText {
+    pub kind: FahrtEventKind,
+    pub text: String,
+    /// Distance between the standstill and the entry in m.
+    pub distance: f32,
+}
+
+/// A continuous period in which the train is standing according to the [StandstillOptions].
+#[derive(PartialEq, Debug, Clone)]
+pub struct StandstillPeriod {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub duration: Duration,
+    pub fahrt_km: f32,
+    /// [None] if the route does not contain any entries with a `FahrtText`.
+    pub nearest_text: Option<NearestText>,
+}
+
+pub(crate) fn find_standstill_periods(values: &[&ResultValue], standstill: &StandstillOptions) -> Vec<StandstillPeriod> {
+    let texts: Vec<(f32, FahrtEventKind, &String)> = values.iter()
+        .filter_map(|ResultValue::FahrtEintrag(fahrt_eintrag)| {
+            if fahrt_eintrag.fahrt_text.is_empty() {
+                None
+            } else {
+                Some((fahrt_eintrag.fahrt_weg, fahrt_eintrag.fahrt_typ.into(), &fahrt_eintrag.fahrt_text))
+            }
+        })
+        .collect();
+
+    find_standstills(&find_driving_intervals(values, standstill)).into_iter()
+        .map(|(start, end)| {
+            let ResultValue::FahrtEintrag(first) = values[start];
+            let ResultValue::FahrtEintrag(last) = values[end];
+
+            let nearest_text = texts.iter()
+                .map(|(fahrt_weg, kind, text)| (kind, text, (fahrt_weg - first.fahrt_weg).abs()))
+                .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+                .map(|(kind, text, distance)| NearestText {
+                    kind: *kind,
+                    text: (*text).clone(),
+                    distance,
+                });
+
+            StandstillPeriod {
+                start_time: first.fahrt_zeit,
+                end_time: last.fahrt_zeit,
+                duration: last.fahrt_zeit - first.fahrt_zeit,
+                fahrt_km: first.fahrt_km,
+                nearest_text,
+            }
+        })
+        .collect()
+}
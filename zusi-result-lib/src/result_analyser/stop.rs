@@ -0,0 +1,52 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fahrt_event::FahrtEventKind;
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::helpers::{find_driving_intervals, find_standstills};
+
+/// A standstill of the train linked to the nearest station.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Stop {
+    /// Name of the nearest station or [None] if the route does not contain any stations.
+    pub station: Option<String>,
+    pub arrival: PrimitiveDateTime,
+    pub departure: PrimitiveDateTime,
+    pub dwell_time: Duration,
+    /// Whether the timetable contains a stop at the station during this standstill.
+    pub scheduled: bool,
+}
+
+pub(crate) fn find_stops(values: &[&ResultValue], standstill: &StandstillOptions) -> Vec<Stop> {
+    let stations: Vec<(usize, f32, &String, bool)> = values.iter().enumerate()
+        .filter_map(|(index, ResultValue::FahrtEintrag(fahrt_eintrag))| {
+            if FahrtEventKind::from(fahrt_eintrag.fahrt_typ) == FahrtEventKind::Station && !fahrt_eintrag.fahrt_text.is_empty() {
+                let scheduled = fahrt_eintrag.fahrt_fpl_ank.is_some() || fahrt_eintrag.fahrt_fpl_abf.is_some();
+                Some((index, fahrt_eintrag.fahrt_weg, &fahrt_eintrag.fahrt_text, scheduled))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    find_standstills(&find_driving_intervals(values, standstill)).into_iter()
+        .map(|(start, end)| {
+            let ResultValue::FahrtEintrag(arrival) = values[start];
+            let ResultValue::FahrtEintrag(departure) = values[end];
+
+            let scheduled_station = stations.iter()
+                .find(|(index, _, _, scheduled)| *scheduled && (start..=end).contains(index));
+            let station = scheduled_station.or_else(|| stations.iter().min_by(|(_, a, _, _), (_, b, _, _)|
+                (a - arrival.fahrt_weg).abs().total_cmp(&(b - arrival.fahrt_weg).abs())
+            ));
+
+            Stop {
+                station: station.map(|(_, _, name, _)| (*name).clone()),
+                arrival: arrival.fahrt_zeit,
+                departure: departure.fahrt_zeit,
+                dwell_time: departure.fahrt_zeit - arrival.fahrt_zeit,
+                scheduled: scheduled_station.is_some(),
+            }
+        })
+        .collect()
+}
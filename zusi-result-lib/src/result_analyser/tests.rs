@@ -1,9 +1,25 @@
+use std::collections::HashMap;
+
 use time::Duration;
 use time::macros::datetime;
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
 use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
 
-use crate::result_analyser::{AnalyseError, PureAverageSpeedAlgorithm, ResultAnalyser};
+use crate::fahrt_event::{FahrtEventKind, FahrtParameterFlag};
+use crate::result_analyser::acceleration::BrakingEpisode;
+use crate::result_analyser::emergency_braking::{EmergencyBraking, EmergencyBrakingKind, DEFAULT_EMERGENCY_DECELERATION};
+use crate::result_analyser::analysis_options::{AnalysisOptions, StandstillOptions, ValidityPolicy};
+use crate::result_analyser::{AnalyseError, DrivingTimeAlgorithm, PureAverageSpeedAlgorithm, ResultAnalyser};
+use crate::result_analyser::minimum_run_time::MinimumRunTimeOptions;
+use crate::result_analyser::section::Section;
+use crate::result_analyser::signal_restriction::SignalRestriction;
+use crate::result_analyser::speed_distribution::{SpeedPercentiles, SpeedWeighting};
+use crate::result_analyser::punctuality::{Punctuality, StationPunctuality};
+use crate::result_analyser::speed_violation::{SpeedLimit, SpeedViolation};
+use crate::result_analyser::standstill::{NearestText, StandstillPeriod};
+use crate::result_analyser::stop::Stop;
+use crate::result_analyser::train_protection::{Supervision, SupervisionEpisode, DEFAULT_SUPERVISION_SPEEDS, RESTRICTIVE_SPEED};
+use crate::result_analyser::validation::{Anomaly, AnomalyKind, Severity, ValidationOptions, ValidationReport};
 
 #[test]
 fn create_result_analyser_from_ref() {
@@ -15,6 +31,82 @@ fn create_result_analyser_from_ref() {
     let _analyser = ResultAnalyser::new(&result);
 }
 
+#[test]
+fn test_eq_ignores_cache() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(8.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(8.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    analyser.distance().unwrap();
+    assert_eq!(analyser, ResultAnalyser::new(&result));
+    assert_ne!(analyser, ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        filter_driving_time: false,
+        ..AnalysisOptions::default()
+    }));
+}
+
+#[test]
+fn test_caching() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(8.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(8.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(6000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:33))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(6000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:38))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+
+    for _ in 0..2 {
+        assert_eq!(analyser.distance().unwrap(), 6000.);
+        assert_eq!(analyser.average_speed().unwrap(), 5.);
+        assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::PureDrivingTime).unwrap(), 6.6666665);
+        assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::WeightedLocalSpeeds).unwrap(), 6.6666665);
+        assert_eq!(analyser.driving_time().unwrap(), Duration::minutes(20));
+        assert_eq!(analyser.pure_driving_time().unwrap(), Duration::minutes(15));
+        assert_eq!(analyser.energy_consumption_per_distance().unwrap(), 1. / 6.);
+        assert_eq!(analyser.energy_consumption_per_pure_driving_time().unwrap(), 4.);
+        assert_eq!(analyser.max_speed().unwrap(), 8.);
+    }
+}
+
 #[test]
 fn test_distance_2() {
     let result = ZusiResult::builder()
@@ -262,6 +354,87 @@ fn test_driving_time_0() {
     assert_eq!(analyser.distance(), Err(AnalyseError::NoEntries));
 }
 
+#[test]
+fn test_pure_average_speed_variants() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::PureDrivingTime).unwrap(), 10.);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::WeightedLocalSpeeds).unwrap(), 11.666667);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds).unwrap(), 12.857142);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::MedianLocalSpeed).unwrap(), 10.);
+
+    let pure_average_speeds = analyser.pure_average_speeds();
+    assert_eq!(pure_average_speeds.values.len(), PureAverageSpeedAlgorithm::ALL.len());
+    assert_eq!(pure_average_speeds.get(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds), Some(Ok(12.857142)));
+    assert_eq!(pure_average_speeds.spread(), Some(2.8571424));
+}
+
+#[test]
+fn test_pure_average_speed_variants_1() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds), Err(AnalyseError::ZeroDistance));
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::MedianLocalSpeed), Err(AnalyseError::ZeroDrivingTime));
+    assert_eq!(analyser.pure_average_speeds().spread(), None);
+}
+
+#[test]
+fn test_pure_average_speed_variants_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::MedianLocalSpeed), Err(AnalyseError::NoEntries));
+}
+
 #[test]
 fn test_pure_driving_time() {
     let result = ZusiResult::builder()
@@ -306,6 +479,64 @@ fn test_pure_driving_time() {
     assert_eq!(analyser.pure_driving_time().unwrap(), Duration::minutes(245));
 }
 
+#[test]
+fn test_pure_driving_time_with_standstill() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(0.05)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(0.05)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let threshold = StandstillOptions {
+        speed_threshold: 0.1,
+        min_duration: Duration::ZERO,
+    };
+    let min_duration = StandstillOptions {
+        speed_threshold: 0.1,
+        min_duration: Duration::minutes(3),
+    };
+
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.pure_driving_time().unwrap(), Duration::minutes(4));
+    assert_eq!(analyser.pure_driving_time_with_standstill(&StandstillOptions::default()).unwrap(), Duration::minutes(4));
+    assert_eq!(analyser.pure_driving_time_with_standstill(&threshold).unwrap(), Duration::minutes(2));
+    assert_eq!(analyser.pure_driving_time_with_standstill(&min_duration).unwrap(), Duration::minutes(4));
+    assert_eq!(analyser.pure_average_speed_with_standstill(PureAverageSpeedAlgorithm::PureDrivingTime, &threshold).unwrap(), 10.);
+    assert_eq!(analyser.pure_average_speed_with_standstill(PureAverageSpeedAlgorithm::WeightedLocalSpeeds, &threshold).unwrap(), 5.0125);
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        standstill: threshold,
+        ..AnalysisOptions::default()
+    });
+    assert_eq!(analyser.pure_driving_time().unwrap(), Duration::minutes(2));
+    assert_eq!(analyser.pure_average_speed(PureAverageSpeedAlgorithm::PureDrivingTime).unwrap(), 10.);
+    assert_eq!(analyser.pure_driving_time_with_standstill(&StandstillOptions::default()).unwrap(), Duration::minutes(4));
+}
+
 #[test]
 fn test_pure_driving_time_1() {
     let result = ZusiResult::builder()
@@ -332,3 +563,1869 @@ fn test_pure_driving_time_0() {
     let analyser = ResultAnalyser::new(result);
     assert_eq!(analyser.pure_driving_time(), Err(AnalyseError::NoEntries));
 }
+
+#[test]
+fn test_minimum_run_time() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20:30))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.minimum_run_time().unwrap(), Duration::seconds(140));
+    assert_eq!(analyser.run_time_reserve().unwrap(), Duration::seconds(10));
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        minimum_run_time: MinimumRunTimeOptions {
+            acceleration: 1.,
+            deceleration: 1.,
+        },
+        ..AnalysisOptions::default()
+    });
+    assert_eq!(analyser.minimum_run_time().unwrap(), Duration::seconds(120));
+    assert_eq!(analyser.run_time_reserve().unwrap(), Duration::seconds(30));
+}
+
+#[test]
+fn test_minimum_run_time_overspeeding() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(25.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:40))
+                .fahrt_speed(25.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:20))
+                .fahrt_speed(25.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.minimum_run_time().unwrap(), Duration::seconds(100));
+    assert_eq!(analyser.run_time_reserve().unwrap(), Duration::seconds(-20));
+}
+
+#[test]
+fn test_minimum_run_time_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.minimum_run_time(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.run_time_reserve(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_speed_violations() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(22.2222)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .fahrt_km(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(250.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(25.)
+                .fahrt_speed_strecke(22.2222)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .fahrt_km(10.25)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:15))
+                .fahrt_speed(-1.)
+                .fahrt_speed_strecke(-1.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(520.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(27.)
+                .fahrt_speed_strecke(33.3333)
+                .fahrt_speed_signal(22.)
+                .fahrt_speed_zugsicherung(45.8333)
+                .fahrt_km(10.52)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(780.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(22.)
+                .fahrt_speed_strecke(33.3333)
+                .fahrt_speed_signal(22.)
+                .fahrt_speed_zugsicherung(45.8333)
+                .fahrt_km(10.78)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:40))
+                .fahrt_speed(14.)
+                .fahrt_speed_strecke(33.3333)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(12.5)
+                .fahrt_km(11.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.speed_violations().unwrap(), vec![
+        SpeedViolation {
+            start_time: datetime!(2019-01-01 23:18:10),
+            end_time: datetime!(2019-01-01 23:18:20),
+            start_km: 10.25,
+            end_km: 10.52,
+            max_overspeed: 5.,
+            limit: SpeedLimit::Signal,
+            limit_speed: 22.,
+        },
+        SpeedViolation {
+            start_time: datetime!(2019-01-01 23:18:40),
+            end_time: datetime!(2019-01-01 23:18:40),
+            start_km: 11.,
+            end_km: 11.,
+            max_overspeed: 1.5,
+            limit: SpeedLimit::Zugsicherung,
+            limit_speed: 12.5,
+        },
+    ]);
+}
+
+#[test]
+fn test_speed_violations_no_limit() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(-1.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.speed_violations().unwrap(), vec![]);
+}
+
+#[test]
+fn test_speed_violations_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.speed_violations(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_signal_restrictions() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(5)
+                .fahrt_text("Sbk 410 410".into())
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:40))
+                .fahrt_speed(10.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(15.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20:20))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(5)
+                .fahrt_text("Asig".into())
+                .fahrt_weg(2200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20:40))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let signal_restrictions = analyser.signal_restrictions().unwrap();
+    assert_eq!(signal_restrictions.restrictions, vec![
+        SignalRestriction {
+            start_time: datetime!(2019-01-01 23:18:20),
+            end_time: datetime!(2019-01-01 23:19:40),
+            duration: Duration::seconds(80),
+            distance: 800.,
+            limit_speed: 10.,
+            signal: Some("Sbk 410 410".into()),
+            time_lost: Duration::seconds(40),
+        },
+        SignalRestriction {
+            start_time: datetime!(2019-01-01 23:19:40),
+            end_time: datetime!(2019-01-01 23:20:20),
+            duration: Duration::seconds(40),
+            distance: 600.,
+            limit_speed: 15.,
+            signal: Some("Sbk 410 410".into()),
+            time_lost: Duration::seconds(10),
+        },
+    ]);
+    assert_eq!(signal_restrictions.total_time_lost, Duration::seconds(50));
+}
+
+#[test]
+fn test_signal_restrictions_with_stop() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_text("Hmb-Harburg".into())
+                .fahrt_fpl_ank(Some(43466.9695))
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:25))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:45))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:59))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .build()),
+        ])
+        .build();
+
+    // the scheduled stop is not counted as time lost
+    let analyser = ResultAnalyser::new(result);
+    let signal_restrictions = analyser.signal_restrictions().unwrap();
+    assert_eq!(signal_restrictions.restrictions.len(), 1);
+    assert_eq!(signal_restrictions.restrictions[0].duration, Duration::seconds(59));
+    assert_eq!(signal_restrictions.total_time_lost, Duration::seconds(9));
+
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_text("Hmb-Harburg".into())
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:25))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:45))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:59))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .build()),
+        ])
+        .build();
+
+    // waiting without a scheduled stop is counted
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.signal_restrictions().unwrap().total_time_lost, Duration::seconds(29));
+}
+
+#[test]
+fn test_signal_restrictions_until_end() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(10.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let signal_restrictions = analyser.signal_restrictions().unwrap();
+    assert_eq!(signal_restrictions.restrictions, vec![
+        SignalRestriction {
+            start_time: datetime!(2019-01-01 23:18:00),
+            end_time: datetime!(2019-01-01 23:18:10),
+            duration: Duration::seconds(10),
+            distance: 100.,
+            limit_speed: 0.,
+            signal: None,
+            time_lost: Duration::seconds(5),
+        },
+    ]);
+}
+
+#[test]
+fn test_signal_restrictions_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.signal_restrictions(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_train_protection() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(45.8333)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:40))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(500.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:50))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(f32::NAN)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let train_protection = analyser.train_protection(&DEFAULT_SUPERVISION_SPEEDS).unwrap();
+    assert_eq!(train_protection.episodes, vec![
+        SupervisionEpisode {
+            start_time: datetime!(2019-01-01 23:18:00),
+            end_time: datetime!(2019-01-01 23:18:10),
+            duration: Duration::seconds(10),
+            distance: 100.,
+            supervision: Supervision::Limit(DEFAULT_SUPERVISION_SPEEDS[2]),
+            restrictive_activation: false,
+        },
+        SupervisionEpisode {
+            start_time: datetime!(2019-01-01 23:18:10),
+            end_time: datetime!(2019-01-01 23:18:20),
+            duration: Duration::seconds(10),
+            distance: 100.,
+            supervision: Supervision::Curve,
+            restrictive_activation: false,
+        },
+        SupervisionEpisode {
+            start_time: datetime!(2019-01-01 23:18:20),
+            end_time: datetime!(2019-01-01 23:18:40),
+            duration: Duration::seconds(20),
+            distance: 200.,
+            supervision: Supervision::Limit(RESTRICTIVE_SPEED),
+            restrictive_activation: true,
+        },
+        SupervisionEpisode {
+            start_time: datetime!(2019-01-01 23:18:50),
+            end_time: datetime!(2019-01-01 23:19:00),
+            duration: Duration::seconds(10),
+            distance: 100.,
+            supervision: Supervision::Limit(RESTRICTIVE_SPEED),
+            restrictive_activation: true,
+        },
+    ]);
+    assert_eq!(train_protection.totals.restrictive_activations, 2);
+    assert_eq!(
+        train_protection.totals.totals.iter().map(|total| (total.duration, total.distance)).collect::<Vec<_>>(),
+        vec![
+            (Duration::seconds(30), 300.),
+            (Duration::ZERO, 0.),
+            (Duration::seconds(10), 100.),
+            (Duration::seconds(10), 100.),
+        ],
+    );
+}
+
+#[test]
+fn test_train_protection_restrictive_sub_supervision() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(6.)
+                .fahrt_speed_zugsicherung(6.9444)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(160.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(260.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let train_protection = analyser.train_protection(&DEFAULT_SUPERVISION_SPEEDS).unwrap();
+    assert_eq!(
+        train_protection.episodes.iter().map(|episode| (episode.supervision, episode.restrictive_activation)).collect::<Vec<_>>(),
+        vec![
+            (Supervision::Limit(RESTRICTIVE_SPEED), true),
+            (Supervision::Curve, false),
+            (Supervision::Limit(RESTRICTIVE_SPEED), false),
+        ],
+    );
+    assert_eq!(train_protection.totals.restrictive_activations, 1);
+}
+
+#[test]
+fn test_train_protection_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.train_protection(&DEFAULT_SUPERVISION_SPEEDS), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_emergency_brakings() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(30.)
+                .fahrt_km(1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(250.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(20.)
+                .fahrt_km(1.25)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(350.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(0.)
+                .fahrt_km(1.35)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(350.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(0.)
+                .fahrt_km(1.35)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(400.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:40))
+                .fahrt_speed(10.)
+                .fahrt_km(1.4)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(500.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:50))
+                .fahrt_speed(20.)
+                .fahrt_km(1.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(1)
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_speed(10.)
+                .fahrt_km(1.6)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(650.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:20))
+                .fahrt_speed(0.)
+                .fahrt_km(1.65)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(650.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:30))
+                .fahrt_speed(0.)
+                .fahrt_km(1.65)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(700.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:40))
+                .fahrt_speed(10.)
+                .fahrt_km(1.7)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21:20))
+                .fahrt_speed(0.)
+                .fahrt_km(2.2)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.emergency_brakings(DEFAULT_EMERGENCY_DECELERATION).unwrap(), vec![
+        EmergencyBraking {
+            kind: EmergencyBrakingKind::EmergencyStop,
+            onset_time: datetime!(2019-01-01 23:18:00),
+            onset_km: 1.,
+            onset_speed: 30.,
+            stop_time: Some(datetime!(2019-01-01 23:18:20)),
+            stopping_distance: Some(350.),
+        },
+        EmergencyBraking {
+            kind: EmergencyBrakingKind::ForcedBraking,
+            onset_time: datetime!(2019-01-01 23:18:50),
+            onset_km: 1.5,
+            onset_speed: 20.,
+            stop_time: Some(datetime!(2019-01-01 23:19:20)),
+            stopping_distance: Some(150.),
+        },
+    ]);
+    assert_eq!(analyser.emergency_braking_counts(DEFAULT_EMERGENCY_DECELERATION).unwrap(), HashMap::from([
+        (EmergencyBrakingKind::EmergencyStop, 1),
+        (EmergencyBrakingKind::ForcedBraking, 1),
+    ]));
+    assert_eq!(analyser.emergency_braking_counts(2.).unwrap(), HashMap::from([
+        (EmergencyBrakingKind::ForcedBraking, 1),
+    ]));
+}
+
+#[test]
+fn test_emergency_brakings_forced_braking_after_stop() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(1)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.emergency_brakings(DEFAULT_EMERGENCY_DECELERATION).unwrap(), vec![
+        EmergencyBraking {
+            kind: EmergencyBrakingKind::ForcedBraking,
+            onset_time: datetime!(2019-01-01 23:18:00),
+            onset_km: 0.,
+            onset_speed: 20.,
+            stop_time: Some(datetime!(2019-01-01 23:18:10)),
+            stopping_distance: Some(100.),
+        },
+    ]);
+}
+
+#[test]
+fn test_emergency_brakings_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.emergency_brakings(DEFAULT_EMERGENCY_DECELERATION), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_punctuality() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2018-11-06 7:30:10))
+                .fahrt_text("A-Dorf".into())
+                .fahrt_fpl_abf(Some(43410.3125))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2018-11-06 7:30:40))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(300.)
+                .fahrt_zeit(datetime!(2018-11-06 7:31:00))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2018-11-06 7:33:00))
+                .fahrt_speed(25.)
+                .fahrt_text("Abzw B".into())
+                .fahrt_fpl_ank(Some(43410.31640625))
+                .fahrt_fpl_abf(Some(43410.31640625))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5000.)
+                .fahrt_zeit(datetime!(2018-11-06 7:36:00))
+                .fahrt_speed(5.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:00))
+                .fahrt_text("C-Stadt".into())
+                .fahrt_fpl_ank(Some(43410.3203125))
+                .fahrt_fpl_abf(Some(43410.32421875))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:10))
+                .fahrt_text("C-Stadt".into())
+                .fahrt_fpl_ank(Some(43410.3203125))
+                .fahrt_fpl_abf(Some(43410.32421875))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:30))
+                .fahrt_speed(-1.)
+                .fahrt_text("C-Stadt".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:42:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5100.)
+                .fahrt_zeit(datetime!(2018-11-06 7:42:20))
+                .fahrt_speed(6.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.punctuality().unwrap(), Punctuality {
+        stations: vec![
+            StationPunctuality {
+                name: "A-Dorf".into(),
+                scheduled_arrival: None,
+                scheduled_departure: Some(datetime!(2018-11-06 7:30)),
+                actual_arrival: datetime!(2018-11-06 7:30:10),
+                actual_departure: datetime!(2018-11-06 7:30:40),
+                arrival_delay: None,
+                departure_delay: Some(Duration::seconds(40)),
+            },
+            StationPunctuality {
+                name: "Abzw B".into(),
+                scheduled_arrival: Some(datetime!(2018-11-06 7:35:38)),
+                scheduled_departure: Some(datetime!(2018-11-06 7:35:38)),
+                actual_arrival: datetime!(2018-11-06 7:33),
+                actual_departure: datetime!(2018-11-06 7:33),
+                arrival_delay: Some(Duration::seconds(-158)),
+                departure_delay: Some(Duration::seconds(-158)),
+            },
+            StationPunctuality {
+                name: "C-Stadt".into(),
+                scheduled_arrival: Some(datetime!(2018-11-06 7:41:15)),
+                scheduled_departure: Some(datetime!(2018-11-06 7:46:53)),
+                actual_arrival: datetime!(2018-11-06 7:37),
+                actual_departure: datetime!(2018-11-06 7:42),
+                arrival_delay: Some(Duration::seconds(-255)),
+                departure_delay: Some(Duration::seconds(-293)),
+            },
+        ],
+        max_delay: Some(Duration::seconds(40)),
+        final_delay: Some(Duration::seconds(-255)),
+    });
+}
+
+#[test]
+fn test_punctuality_trailing_departure() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4900.)
+                .fahrt_zeit(datetime!(2018-11-06 7:36:00))
+                .fahrt_speed(5.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:00))
+                .fahrt_text("C-Stadt".into())
+                .fahrt_fpl_ank(Some(43410.3203125))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(5020.)
+                .fahrt_zeit(datetime!(2018-11-06 7:37:10))
+                .fahrt_fpl_abf(Some(43410.32421875))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let punctuality = analyser.punctuality().unwrap();
+    assert_eq!(punctuality.stations.iter().map(|station| station.name.as_str()).collect::<Vec<_>>(), vec!["C-Stadt"]);
+    assert_eq!(punctuality.final_delay, Some(Duration::seconds(-255)));
+}
+
+#[test]
+fn test_punctuality_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.punctuality(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_stops() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_text("A-Dorf".into())
+                .fahrt_fpl_abf(Some(43466.97))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:10))
+                .fahrt_speed(15.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1900.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21:10))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1900.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21:40))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22:00))
+                .fahrt_speed(3.)
+                .fahrt_text("Abzw B".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:25:00))
+                .fahrt_speed(4.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:26:00))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.stops().unwrap(), vec![
+        Stop {
+            station: Some("A-Dorf".into()),
+            arrival: datetime!(2019-01-01 23:18:00),
+            departure: datetime!(2019-01-01 23:19:00),
+            dwell_time: Duration::minutes(1),
+            scheduled: true,
+        },
+        Stop {
+            station: Some("Abzw B".into()),
+            arrival: datetime!(2019-01-01 23:21:10),
+            departure: datetime!(2019-01-01 23:21:40),
+            dwell_time: Duration::seconds(30),
+            scheduled: false,
+        },
+    ]);
+}
+
+#[test]
+fn test_stops_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.stops(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_standstills_with_standstill_options() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(50.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(50.5)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(0.05)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(51.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:50))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:59))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    // creeping at 0.05 m/s counts as driving by default
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.standstill_periods().unwrap(), vec![]);
+    assert_eq!(analyser.stops().unwrap(), vec![]);
+    assert_eq!(analyser.speed_histogram(&[1.]).unwrap().total_weight(), 59.);
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        standstill: StandstillOptions {
+            speed_threshold: 0.1,
+            min_duration: Duration::ZERO,
+        },
+        ..AnalysisOptions::default()
+    });
+    let standstill_periods = analyser.standstill_periods().unwrap();
+    assert_eq!(standstill_periods.len(), 1);
+    assert_eq!(standstill_periods[0].duration, Duration::seconds(40));
+    assert_eq!(analyser.stops().unwrap().len(), 1);
+    assert_eq!(analyser.speed_histogram(&[1.]).unwrap().total_weight(), 19.);
+    assert_eq!(analyser.pure_driving_time().unwrap(), Duration::seconds(19));
+}
+
+#[test]
+fn test_fahrt_parameter_flag_counts() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_parameter(48)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_parameter(16)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_parameter(1)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(3)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_parameter(3)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.fahrt_parameter_flag_counts(), HashMap::from([
+        ((FahrtEventKind::Station, FahrtParameterFlag::Bit0), 1),
+        ((FahrtEventKind::TrainProtectionOperation, FahrtParameterFlag::PzbWachsam), 2),
+        ((FahrtEventKind::TrainProtectionOperation, FahrtParameterFlag::PzbWachsamHeld), 1),
+        ((FahrtEventKind::RuleViolation, FahrtParameterFlag::Bit0), 1),
+        ((FahrtEventKind::RuleViolation, FahrtParameterFlag::Severe), 1),
+    ]));
+}
+
+#[test]
+fn test_energy_consumption() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(72_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:15))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.energy_consumption(), 20.);
+    assert_eq!(analyser.energy_consumption_per_distance().unwrap(), 5.);
+    assert_eq!(analyser.energy_consumption_per_pure_driving_time().unwrap(), 80.);
+}
+
+#[test]
+fn test_energy_consumption_zero_distance() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:15))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.energy_consumption(), 1.);
+    assert_eq!(analyser.energy_consumption_per_distance(), Err(AnalyseError::ZeroDistance));
+    assert_eq!(analyser.energy_consumption_per_pure_driving_time(), Err(AnalyseError::ZeroDrivingTime));
+}
+
+#[test]
+fn test_energy_consumption_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.energy_consumption(), 1.);
+    assert_eq!(analyser.energy_consumption_per_distance(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.energy_consumption_per_pure_driving_time(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_acceleration_profile() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:00))
+                .fahrt_speed(0.)
+                .fahrt_km(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:20))
+                .fahrt_speed(10.)
+                .fahrt_km(10.1)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:25))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(120.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:30))
+                .fahrt_speed(2.)
+                .fahrt_km(10.12)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(120.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:30))
+                .fahrt_speed(2.)
+                .fahrt_km(10.12)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(121.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:31))
+                .fahrt_speed(0.)
+                .fahrt_km(10.121)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(121.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:31))
+                .fahrt_speed(0.)
+                .fahrt_km(10.121)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let profile = analyser.acceleration_profile(&[-1., 0., 1.], 0.5).unwrap();
+
+    assert_eq!(profile.max_acceleration, 0.5);
+    assert_eq!(profile.max_deceleration, 2.);
+    assert_eq!(profile.histogram.weights, vec![1., 10., 80., 0.]);
+    assert_eq!(profile.hard_braking_episodes, vec![
+        BrakingEpisode {
+            start_time: datetime!(2019-01-01 23:00:20),
+            end_time: datetime!(2019-01-01 23:00:31),
+            start_km: 10.1,
+            end_km: 10.121,
+            start_speed: 10.,
+            end_speed: 0.,
+            max_deceleration: 2.,
+        },
+    ]);
+}
+
+#[test]
+fn test_acceleration_profile_1() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:00))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.acceleration_profile(&[], 0.5), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_speed_distribution() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:20))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:30))
+                .fahrt_speed(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:40))
+                .fahrt_speed(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:02:00))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:12:00))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.max_speed().unwrap(), 30.);
+    assert_eq!(analyser.speed_percentiles(SpeedWeighting::Time).unwrap(), SpeedPercentiles {
+        p50: 10.,
+        p90: 20.,
+        p99: 30.,
+    });
+    assert_eq!(analyser.speed_percentiles(SpeedWeighting::Distance).unwrap(), SpeedPercentiles {
+        p50: 10.,
+        p90: 30.,
+        p99: 30.,
+    });
+    assert_eq!(analyser.speed_histogram(&[15., 25.]).unwrap().weights, vec![80., 30., 10.]);
+}
+
+#[test]
+fn test_speed_distribution_standing() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:00))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.max_speed().unwrap(), 0.);
+    assert_eq!(analyser.speed_percentiles(SpeedWeighting::Time), Err(AnalyseError::ZeroDrivingTime));
+    assert_eq!(analyser.speed_percentiles(SpeedWeighting::Distance), Err(AnalyseError::ZeroDistance));
+    assert_eq!(analyser.speed_histogram(&[15.]).unwrap().weights, vec![0., 0.]);
+}
+
+#[test]
+fn test_speed_distribution_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.max_speed(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.speed_percentiles(SpeedWeighting::Time), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.speed_histogram(&[15.]), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_standstill_periods() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_km(5.)
+                .fahrt_text("A-Dorf".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_km(5.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20:00))
+                .fahrt_speed(15.)
+                .fahrt_km(5.99)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1900.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21:10))
+                .fahrt_km(6.89)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1900.)
+                .fahrt_zeit(datetime!(2019-01-01 23:23:40))
+                .fahrt_km(6.89)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(5)
+                .fahrt_weg(1950.)
+                .fahrt_zeit(datetime!(2019-01-01 23:24:00))
+                .fahrt_speed(3.)
+                .fahrt_km(6.94)
+                .fahrt_text("Asig C".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2500.)
+                .fahrt_zeit(datetime!(2019-01-01 23:25:00))
+                .fahrt_speed(10.)
+                .fahrt_km(7.49)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.standstill_periods().unwrap(), vec![
+        StandstillPeriod {
+            start_time: datetime!(2019-01-01 23:18:00),
+            end_time: datetime!(2019-01-01 23:19:00),
+            duration: Duration::minutes(1),
+            fahrt_km: 5.,
+            nearest_text: Some(NearestText {
+                kind: FahrtEventKind::Station,
+                text: "A-Dorf".into(),
+                distance: 0.,
+            }),
+        },
+        StandstillPeriod {
+            start_time: datetime!(2019-01-01 23:21:10),
+            end_time: datetime!(2019-01-01 23:23:40),
+            duration: Duration::seconds(150),
+            fahrt_km: 6.89,
+            nearest_text: Some(NearestText {
+                kind: FahrtEventKind::Signal,
+                text: "Asig C".into(),
+                distance: 50.,
+            }),
+        },
+    ]);
+}
+
+#[test]
+fn test_standstill_periods_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.standstill_periods(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_sections() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:10:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:12:00))
+                .fahrt_text("A-Dorf".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:12:00))
+                .fahrt_text("A-Dorf".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:14:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:14:30))
+                .fahrt_speed(-1.)
+                .fahrt_text("A-Dorf".into())
+                .fahrt_parameter(1)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(4000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19:00))
+                .fahrt_speed(10.)
+                .fahrt_text("B-Stadt".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(5000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20:40))
+                .fahrt_speed(10.)
+                .fahrt_text("C-Hausen".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(6000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22:20))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.sections().unwrap(), vec![
+        Section {
+            from: "A-Dorf".into(),
+            to: "B-Stadt".into(),
+            start_time: datetime!(2019-01-01 23:12:00),
+            end_time: datetime!(2019-01-01 23:19:00),
+            distance: 3000.,
+            driving_time: Duration::minutes(7),
+            pure_driving_time: Duration::minutes(5),
+            average_speed: Some(3000. / 420.),
+            pure_average_speed: Some(10.),
+            minimum_run_time: Duration::seconds(310),
+            run_time_reserve: Duration::seconds(-10),
+        },
+        Section {
+            from: "B-Stadt".into(),
+            to: "C-Hausen".into(),
+            start_time: datetime!(2019-01-01 23:19:00),
+            end_time: datetime!(2019-01-01 23:20:40),
+            distance: 1000.,
+            driving_time: Duration::seconds(100),
+            pure_driving_time: Duration::seconds(100),
+            average_speed: Some(10.),
+            pure_average_speed: Some(10.),
+            minimum_run_time: Duration::seconds(100),
+            run_time_reserve: Duration::ZERO,
+        },
+    ]);
+}
+
+#[test]
+fn test_sections_with_validity_policy() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-2.)
+                .fahrt_zeit(datetime!(2019-01-01 23:10:00))
+                .fahrt_speed(-2.)
+                .fahrt_text("A-Dorf".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:10:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:11:40))
+                .fahrt_speed(-2.)
+                .fahrt_text("B-Stadt".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:13:20))
+                .fahrt_speed(-2.)
+                .fahrt_text("C-Hausen".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2500.)
+                .fahrt_zeit(datetime!(2019-01-01 23:14:10))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(3000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:15:00))
+                .fahrt_speed(10.)
+                .fahrt_text("D-Feld".into())
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new_with_options(result, AnalysisOptions {
+        validity: ValidityPolicy {
+            invalid_fahrt_weg: vec![-2.],
+            invalid_fahrt_speed: vec![-2.],
+            ..ValidityPolicy::default()
+        },
+        ..AnalysisOptions::default()
+    });
+    // A-Dorf has no position and the section from B-Stadt to C-Hausen has no valid entries
+    let sections = analyser.sections().unwrap();
+    assert_eq!(
+        sections.iter().map(|section| (section.from.as_str(), section.to.as_str(), section.distance)).collect::<Vec<_>>(),
+        vec![("C-Hausen", "D-Feld", 500.)],
+    );
+}
+
+#[test]
+fn test_sections_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.sections(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_driving_time_with_options() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(3)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(1998-01-30 9:21))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(2.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:38))
+                .fahrt_speed(1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.driving_time().unwrap(), Duration::minutes(20));
+    assert_eq!(analyser.average_speed().unwrap(), 1.);
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        filter_driving_time: false,
+        ..AnalysisOptions::default()
+    });
+    assert_eq!(analyser.driving_time().unwrap(), datetime!(2019-01-01 23:38) - datetime!(1998-01-30 9:21));
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        validity: ValidityPolicy {
+            missing_fahrt_speed_is_zero: false,
+            ..ValidityPolicy::default()
+        },
+        ..AnalysisOptions::default()
+    });
+    assert_eq!(analyser.distance().unwrap(), 600.);
+    assert_eq!(analyser.driving_time().unwrap(), Duration::minutes(10));
+}
+
+#[test]
+fn test_validity_policy_sentinels() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(5.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(-9999.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(50.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(5.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    assert_eq!(analyser.max_speed().unwrap(), 50.);
+
+    let analyser = ResultAnalyser::new_with_options(&result, AnalysisOptions {
+        validity: ValidityPolicy {
+            invalid_fahrt_weg: vec![-1., -9999.],
+            ..ValidityPolicy::default()
+        },
+        ..AnalysisOptions::default()
+    });
+    assert_eq!(analyser.max_speed().unwrap(), 5.);
+    assert_eq!(analyser.distance().unwrap(), 300.);
+}
+
+#[test]
+fn test_validate() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(3)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(1998-01-30 9:21))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28:10))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(90.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28:20))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1090.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28:30))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1190.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28:25))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1290.)
+                .fahrt_zeit(datetime!(2019-01-01 23:38:35))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let report = analyser.validate(&ValidationOptions::default()).unwrap();
+    assert_eq!(report, ValidationReport {
+        anomalies: vec![
+            Anomaly {
+                index: 0,
+                severity: Severity::Error,
+                kind: AnomalyKind::OutlierDate {
+                    offset: datetime!(1998-01-30 9:21) - datetime!(2019-01-01 23:28:20),
+                },
+            },
+            Anomaly {
+                index: 2,
+                severity: Severity::Info,
+                kind: AnomalyKind::TimeGap {
+                    gap: Duration::minutes(10),
+                    standing: true,
+                },
+            },
+            Anomaly {
+                index: 4,
+                severity: Severity::Error,
+                kind: AnomalyKind::BackwardFahrtWeg {
+                    previous: 100.,
+                    current: 90.,
+                },
+            },
+            Anomaly {
+                index: 5,
+                severity: Severity::Warning,
+                kind: AnomalyKind::ImplausibleSpeed {
+                    derived_speed: 100.,
+                    recorded_speed: 10.,
+                },
+            },
+            Anomaly {
+                index: 6,
+                severity: Severity::Error,
+                kind: AnomalyKind::NonMonotonicTime {
+                    previous: datetime!(2019-01-01 23:28:30),
+                    current: datetime!(2019-01-01 23:28:25),
+                },
+            },
+            Anomaly {
+                index: 7,
+                severity: Severity::Warning,
+                kind: AnomalyKind::TimeGap {
+                    gap: Duration::seconds(610),
+                    standing: false,
+                },
+            },
+        ],
+    });
+    assert_eq!(report.max_severity(), Some(Severity::Error));
+    assert_eq!(report.count(Severity::Error), 3);
+}
+
+#[test]
+fn test_validate_clean() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(40.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(50.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:02))
+                .fahrt_speed(40.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:02))
+                .fahrt_speed(40.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let report = analyser.validate(&ValidationOptions::default()).unwrap();
+    assert!(report.is_clean());
+    assert_eq!(report.max_severity(), None);
+}
+
+#[test]
+fn test_validate_backward_distance() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(0.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(99.6)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:02))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert!(analyser.validate(&ValidationOptions::default()).unwrap().is_clean());
+
+    let report = analyser.validate(&ValidationOptions {
+        max_backward_distance: 0.,
+        ..ValidationOptions::default()
+    }).unwrap();
+    assert_eq!(report.anomalies, vec![
+        Anomaly {
+            index: 1,
+            severity: Severity::Error,
+            kind: AnomalyKind::BackwardFahrtWeg {
+                previous: 100.,
+                current: 99.6,
+            },
+        },
+    ]);
+}
+
+#[test]
+fn test_validate_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.validate(&ValidationOptions::default()), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_driving_time_excluding_gaps() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-02 1:19))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-02 1:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-02 1:21))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.driving_time().unwrap(), Duration::minutes(123));
+    assert_eq!(analyser.driving_time_excluding_gaps(Duration::minutes(5)).unwrap(), Duration::minutes(4));
+    assert_eq!(
+        analyser.driving_time_with_algorithm(DrivingTimeAlgorithm::ExcludingGaps(Duration::hours(3))).unwrap(),
+        Duration::minutes(124),
+    );
+    assert_eq!(
+        analyser.driving_time_with_algorithm(DrivingTimeAlgorithm::FirstToLastEntry).unwrap(),
+        Duration::minutes(123),
+    );
+}
+
+#[test]
+fn test_driving_time_excluding_gaps_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.driving_time_excluding_gaps(Duration::minutes(5)), Err(AnalyseError::NoEntries));
+}
@@ -0,0 +1,180 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+/// Speed in m/s supervised by the PZB in restrictive mode (45 km/h).
+pub const RESTRICTIVE_SPEED: f32 = 45. / 3.6;
+
+/// Default supervision speeds in m/s for [train_protection](crate::result_analyser::ResultAnalyser::train_protection),
+/// which are the constant speeds supervised by the PZB (45, 85 and 165 km/h).
+pub const DEFAULT_SUPERVISION_SPEEDS: [f32; 3] = [RESTRICTIVE_SPEED, 85. / 3.6, 165. / 3.6];
+
+/// `FahrtspZugsicherung` is rounded to four decimals.
+const SPEED_TOLERANCE: f32 = 0.01;
+
+/// The speed supervised by the train protection system (`FahrtspZugsicherung`).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Supervision {
+    /// One of the given supervision speeds in m/s.
+    Limit(f32),
+    /// Any other speed, usually while a braking curve or the LZB is supervised.
+    Curve,
+}
+
+impl Supervision {
+    /// Returns [None] if the train protection system does not supervise any speed.
+    fn classify(fahrt_speed_zugsicherung: f32, supervision_speeds: &[f32]) -> Option<Supervision> {
+        if fahrt_speed_zugsicherung == -1. || fahrt_speed_zugsicherung.is_nan() {
+            return None;
+        }
+        Some(supervision_speeds.iter()
+            .find(|speed| (*speed - fahrt_speed_zugsicherung).abs() < SPEED_TOLERANCE)
+            .map_or(Supervision::Curve, |speed| Supervision::Limit(*speed)))
+    }
+
+    pub fn is_restrictive(&self) -> bool {
+        matches!(self, Supervision::Limit(speed) if (speed - RESTRICTIVE_SPEED).abs() < SPEED_TOLERANCE)
+    }
+
+    /// Whether the restrictive mode ends with this supervision, which is the case for any constant speed above [RESTRICTIVE_SPEED].
+    /// Lower speeds and braking curves are supervised within the restrictive mode.
+    fn ends_restrictive_mode(&self) -> bool {
+        matches!(self, Supervision::Limit(speed) if *speed > RESTRICTIVE_SPEED + SPEED_TOLERANCE)
+    }
+}
+
+/// A continuous period with the same [Supervision].
+///
+/// The episode lasts from its first entry until the next entry with another supervision,
+/// or until the last entry of the run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SupervisionEpisode {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub duration: Duration,
+    /// Distance driven during the episode in m.
+    pub distance: f32,
+    pub supervision: Supervision,
+    /// Whether the restrictive mode was activated at the beginning of this episode,
+    /// i.e. the episode is supervised at [RESTRICTIVE_SPEED] and follows no or a less restrictive supervision.
+    pub restrictive_activation: bool,
+}
+
+/// Time and distance spent under a [Supervision].
+#[derive(PartialEq, Debug, Clone)]
+pub struct SupervisionTotal {
+    pub supervision: Supervision,
+    pub duration: Duration,
+    /// Distance in m.
+    pub distance: f32,
+}
+
+/// Counts of one or more runs which can be merged.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SupervisionTotals {
+    /// Number of [restrictive activations](SupervisionEpisode::restrictive_activation).
+    pub restrictive_activations: usize,
+    /// One total for each supervision speed in the given order followed by one for [Supervision::Curve].
+    pub totals: Vec<SupervisionTotal>,
+}
+
+impl SupervisionTotals {
+    pub fn new(supervision_speeds: &[f32]) -> SupervisionTotals {
+        Self {
+            restrictive_activations: 0,
+            totals: supervision_speeds.iter()
+                .map(|speed| Supervision::Limit(*speed))
+                .chain([Supervision::Curve])
+                .map(|supervision| SupervisionTotal {
+                    supervision,
+                    duration: Duration::ZERO,
+                    distance: 0.,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn add(&mut self, episode: &SupervisionEpisode) {
+        if episode.restrictive_activation {
+            self.restrictive_activations += 1;
+        }
+        if let Some(total) = self.totals.iter_mut().find(|total| total.supervision == episode.supervision) {
+            total.duration += episode.duration;
+            total.distance += episode.distance;
+        }
+    }
+
+    /// Adds the counts of `other` to these totals.
+    ///
+    /// Panics if both totals do not have the same supervision speeds.
+    pub fn merge(&mut self, other: &SupervisionTotals) {
+        assert!(
+            self.totals.iter().map(|total| total.supervision).eq(other.totals.iter().map(|total| total.supervision)),
+            "totals with different supervision speeds can't be merged",
+        );
+        self.restrictive_activations += other.restrictive_activations;
+        for (total, other_total) in self.totals.iter_mut().zip(other.totals.iter()) {
+            total.duration += other_total.duration;
+            total.distance += other_total.distance;
+        }
+    }
+}
+
+/// All supervision episodes of a run.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TrainProtection {
+    pub episodes: Vec<SupervisionEpisode>,
+    pub totals: SupervisionTotals,
+}
+
+pub(crate) fn find_train_protection(values: &[&ResultValue], supervision_speeds: &[f32]) -> TrainProtection {
+    let mut episodes: Vec<SupervisionEpisode> = vec![];
+    let mut current: Option<SupervisionEpisode> = None;
+    let mut restrictive_mode = false;
+
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in values.iter().enumerate() {
+        let supervision = Supervision::classify(fahrt_eintrag.fahrt_speed_zugsicherung, supervision_speeds);
+        if current.as_ref().is_some_and(|episode| Some(episode.supervision) != supervision) {
+            episodes.push(current.take().unwrap());
+        }
+        let Some(supervision) = supervision else {
+            restrictive_mode = false;
+            continue;
+        };
+        if supervision.ends_restrictive_mode() {
+            restrictive_mode = false;
+        }
+        let restrictive_activation = current.is_none() && supervision.is_restrictive() && !restrictive_mode;
+        if supervision.is_restrictive() {
+            restrictive_mode = true;
+        }
+
+        let episode = current.get_or_insert(SupervisionEpisode {
+            start_time: fahrt_eintrag.fahrt_zeit,
+            end_time: fahrt_eintrag.fahrt_zeit,
+            duration: Duration::ZERO,
+            distance: 0.,
+            supervision,
+            restrictive_activation,
+        });
+
+        if let Some(ResultValue::FahrtEintrag(next)) = values.get(index + 1) {
+            episode.end_time = next.fahrt_zeit;
+            episode.duration += next.fahrt_zeit - fahrt_eintrag.fahrt_zeit;
+            episode.distance += next.fahrt_weg - fahrt_eintrag.fahrt_weg;
+        }
+    }
+
+    if let Some(episode) = current {
+        episodes.push(episode);
+    }
+
+    let mut totals = SupervisionTotals::new(supervision_speeds);
+    for episode in episodes.iter() {
+        totals.add(episode);
+    }
+
+    TrainProtection {
+        episodes,
+        totals,
+    }
+}
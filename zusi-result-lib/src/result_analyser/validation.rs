@@ -0,0 +1,200 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::result_analyser::analysis_options::ValidityPolicy;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub enum Severity {
+    /// The data is unusual but most likely correct.
+    Info,
+    /// The data may distort some metrics.
+    Warning,
+    /// The data is inconsistent and will distort metrics.
+    Error,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum AnomalyKind {
+    /// `FahrtZeit` is earlier than the one of the previous entry.
+    NonMonotonicTime {
+        previous: PrimitiveDateTime,
+        current: PrimitiveDateTime,
+    },
+    /// `FahrtWeg` is smaller than the one of the previous valid entry
+    /// by more than [max_backward_distance](ValidationOptions::max_backward_distance).
+    BackwardFahrtWeg {
+        previous: f32,
+        current: f32,
+    },
+    /// The speed derived from `FahrtWeg` and `FahrtZeit` does not match the recorded `Fahrtsp`
+    /// of this and the previous valid entry. Both speeds are in m/s, the recorded speed is the average of both entries.
+    /// The derived speed assumes an interval of at least one second.
+    ImplausibleSpeed {
+        derived_speed: f32,
+        recorded_speed: f32,
+    },
+    /// The time since the previous entry exceeds [max_time_gap](ValidationOptions::max_time_gap).
+    /// Gaps while standing are usually caused by pausing the simulation, gaps while moving by time jumps.
+    TimeGap {
+        gap: Duration,
+        standing: bool,
+    },
+    /// `FahrtZeit` differs from the median `FahrtZeit` of the run by more than
+    /// [max_date_offset](ValidationOptions::max_date_offset).
+    /// These entries are not considered by all other checks.
+    OutlierDate {
+        offset: Duration,
+    },
+}
+
+impl AnomalyKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            AnomalyKind::NonMonotonicTime { .. } => Severity::Error,
+            AnomalyKind::BackwardFahrtWeg { .. } => Severity::Error,
+            AnomalyKind::ImplausibleSpeed { .. } => Severity::Warning,
+            AnomalyKind::TimeGap { standing: true, .. } => Severity::Info,
+            AnomalyKind::TimeGap { standing: false, .. } => Severity::Warning,
+            AnomalyKind::OutlierDate { .. } => Severity::Error,
+        }
+    }
+}
+
+/// A suspicious [FahrtEintrag](ResultValue::FahrtEintrag).
+#[derive(PartialEq, Debug, Clone)]
+pub struct Anomaly {
+    /// Index of the entry within the [ZusiResult].
+    pub index: usize,
+    pub severity: Severity,
+    pub kind: AnomalyKind,
+}
+
+/// All anomalies of a [ZusiResult] ordered by their entry index.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Returns the highest severity of all anomalies or [None] if there are none.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.anomalies.iter().map(|anomaly| anomaly.severity).max()
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.anomalies.iter().filter(|anomaly| anomaly.severity == severity).count()
+    }
+}
+
+/// Thresholds for [validate](crate::result_analyser::ResultAnalyser::validate).
+#[derive(PartialEq, Debug, Clone)]
+pub struct ValidationOptions {
+    /// Longest time between two entries which is not reported as [AnomalyKind::TimeGap].
+    pub max_time_gap: Duration,
+    /// Highest difference in m/s between derived and recorded speed which is not reported as [AnomalyKind::ImplausibleSpeed].
+    /// As `FahrtZeit` is only recorded in whole seconds, each interval may additionally be one second shorter or longer.
+    pub max_speed_deviation: f32,
+    /// Highest difference to the median `FahrtZeit` which is not reported as [AnomalyKind::OutlierDate].
+    pub max_date_offset: Duration,
+    /// Longest distance in m the train may roll back without being reported as [AnomalyKind::BackwardFahrtWeg].
+    /// Zusi records small rollbacks e.g. when starting on a gradient.
+    pub max_backward_distance: f32,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            max_time_gap: Duration::minutes(5),
+            max_speed_deviation: 10.,
+            max_date_offset: Duration::days(1),
+            max_backward_distance: 1.,
+        }
+    }
+}
+
+pub(crate) fn validate(result: &ZusiResult, policy: &ValidityPolicy, options: &ValidationOptions) -> ValidationReport {
+    let mut anomalies: Vec<Anomaly> = vec![];
+    let mut push = |index: usize, kind: AnomalyKind| anomalies.push(Anomaly {
+        index,
+        severity: kind.severity(),
+        kind,
+    });
+
+    let mut times: Vec<PrimitiveDateTime> = result.value.iter()
+        .map(|ResultValue::FahrtEintrag(fahrt_eintrag)| fahrt_eintrag.fahrt_zeit)
+        .collect();
+    times.sort();
+    let median_time = times.get(times.len() / 2).copied();
+
+    let mut previous: Option<usize> = None;
+    let mut previous_valid: Option<usize> = None;
+    for (index, ResultValue::FahrtEintrag(fahrt_eintrag)) in result.value.iter().enumerate() {
+        if let Some(median_time) = median_time {
+            let offset = fahrt_eintrag.fahrt_zeit - median_time;
+            if offset.abs() > options.max_date_offset {
+                push(index, AnomalyKind::OutlierDate { offset });
+                continue;
+            }
+        }
+
+        if let Some(previous) = previous {
+            let ResultValue::FahrtEintrag(previous) = &result.value[previous];
+            let gap = fahrt_eintrag.fahrt_zeit - previous.fahrt_zeit;
+            if gap.is_negative() {
+                push(index, AnomalyKind::NonMonotonicTime {
+                    previous: previous.fahrt_zeit,
+                    current: fahrt_eintrag.fahrt_zeit,
+                });
+            } else if gap > options.max_time_gap {
+                push(index, AnomalyKind::TimeGap {
+                    gap,
+                    standing: previous.fahrt_speed <= 0. && fahrt_eintrag.fahrt_speed <= 0.,
+                });
+            }
+        }
+        previous = Some(index);
+
+        if !policy.is_valid(fahrt_eintrag) {
+            continue;
+        }
+
+        if let Some(previous_valid) = previous_valid {
+            let ResultValue::FahrtEintrag(previous_valid) = &result.value[previous_valid];
+            let local_distance = fahrt_eintrag.fahrt_weg - previous_valid.fahrt_weg;
+            let local_driving_time = (fahrt_eintrag.fahrt_zeit - previous_valid.fahrt_zeit).as_seconds_f32();
+            if local_distance < -options.max_backward_distance {
+                push(index, AnomalyKind::BackwardFahrtWeg {
+                    previous: previous_valid.fahrt_weg,
+                    current: fahrt_eintrag.fahrt_weg,
+                });
+            } else if local_distance >= 0. && local_driving_time >= 0. {
+                // FahrtZeit is truncated to whole seconds, so the actual interval may be up to one second shorter or longer
+                let min_derived_speed = local_distance / (local_driving_time + 1.);
+                let max_derived_speed = if local_driving_time > 1. {
+                    local_distance / (local_driving_time - 1.)
+                } else {
+                    f32::INFINITY
+                };
+                // the recorded speed may have changed during the interval
+                let min_recorded_speed = previous_valid.fahrt_speed.min(fahrt_eintrag.fahrt_speed);
+                let max_recorded_speed = previous_valid.fahrt_speed.max(fahrt_eintrag.fahrt_speed);
+                if max_recorded_speed + options.max_speed_deviation < min_derived_speed
+                    || min_recorded_speed - options.max_speed_deviation > max_derived_speed {
+                    push(index, AnomalyKind::ImplausibleSpeed {
+                        derived_speed: local_distance / local_driving_time.max(1.),
+                        recorded_speed: (previous_valid.fahrt_speed + fahrt_eintrag.fahrt_speed) / 2.,
+                    });
+                }
+            }
+        }
+        previous_valid = Some(index);
+    }
+
+    ValidationReport {
+        anomalies,
+    }
+}
@@ -1,23 +1,52 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use time::Duration;
 use zusi_xml_lib::xml::zusi::result::ZusiResult;
 
-use crate::result_analyser::{AnalyseError, PureAverageSpeedAlgorithm, ResultAnalyser};
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::emergency_braking::EmergencyBrakingKind;
+use crate::result_analyser::histogram::Histogram;
+use crate::result_analyser::train_protection::SupervisionTotals;
+use crate::result_analyser::validation::{ValidationOptions, ValidationReport};
+use crate::result_analyser::{AnalyseError, DrivingTimeAlgorithm, PureAverageSpeedAlgorithm, PureAverageSpeeds, ResultAnalyser};
 use crate::result_analyser_group::analyser_group_cache::AnalyserGroupCache;
+use crate::result_analyser_group::distribution::Distribution;
+use crate::result_analyser_group::metric::{AverageSpeed, Distance, DrivingTime, EnergyConsumption, EnergyConsumptionPerDistance, EnergyConsumptionPerPureDrivingTime, Metric, PureAverageSpeed, PureDrivingTime};
 
 #[cfg(test)]
 mod tests;
 mod analyser_group_cache;
+pub mod distribution;
+pub mod group_key;
+pub mod metric;
 
 #[derive(PartialEq, Debug)]
 pub enum CreateAnalyserGroupError {
     NoAnalysers,
 }
 
+/// Result of an aggregation which skipped all routes with an [AnalyseError].
+///
+/// Each aggregate only skips the routes which fail for its own metric, so the failures of different aggregates may differ.
+/// To exclude the same routes from several aggregates, remove them with [retain](ResultAnalyserGroup::retain) first.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TolerantAggregate<T> {
+    /// The aggregate of all successful routes, [None] if no route succeeded.
+    pub value: Option<T>,
+    /// The index and the error of each skipped route.
+    pub failures: Vec<(usize, AnalyseError)>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum RemoveAnalyserError {
+    /// The group would not contain any analysers anymore.
+    NoAnalysersLeft,
+}
+
 #[derive(PartialEq, Debug)]
 pub struct ResultAnalyserGroup<A, R> {
     analysers: Vec<A>,
-    cache: AnalyserGroupCache,
+    cache: AnalyserGroupCache<R>,
     _phantom: PhantomData<R>,
 }
 
@@ -34,38 +63,114 @@ impl<A: AsRef<ResultAnalyser<R>>, R: AsRef<ZusiResult>> ResultAnalyserGroup<A, R
         }
     }
 
-    /// Computes the sum of the distance values for all routes.
-    /// For more details see [distance](ResultAnalyser::distance).
+    pub fn analysers(&self) -> &[A] {
+        &self.analysers
+    }
+
+    /// Adds a route to the group.
+    /// Cached totals and averages are updated instead of being recomputed on the next call.
+    pub fn push(&mut self, analyser: A) {
+        self.cache.add(analyser.as_ref());
+        self.analysers.push(analyser);
+    }
+
+    /// Removes the route at `index` from the group and returns it.
+    /// Cached sums are updated, cached averages will be recomputed on the next call.
     ///
-    /// Errors will be propagated.
-    pub fn total_distance(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.total_distance {
-            return Ok(*value);
+    /// Throws [RemoveAnalyserError::NoAnalysersLeft] if it is the last route of the group.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<A, RemoveAnalyserError> {
+        assert!(index < self.analysers.len(), "index {index} is out of bounds");
+        if self.analysers.len() == 1 {
+            return Err(RemoveAnalyserError::NoAnalysersLeft);
         }
 
-        let mut total_distance = 0.;
+        let analyser = self.analysers.remove(index);
+        self.cache.remove(analyser.as_ref());
+        Ok(analyser)
+    }
 
+    /// Retains only the routes for which `f` returns true, see [remove](ResultAnalyserGroup::remove).
+    ///
+    /// Throws [RemoveAnalyserError::NoAnalysersLeft] if no route would be retained, the group is left unchanged in that case.
+    pub fn retain(&mut self, mut f: impl FnMut(&A) -> bool) -> Result<(), RemoveAnalyserError> {
+        let retained: Vec<bool> = self.analysers.iter().map(&mut f).collect();
+        if !retained.contains(&true) {
+            return Err(RemoveAnalyserError::NoAnalysersLeft);
+        }
+
+        for (analyser, retained) in std::mem::take(&mut self.analysers).into_iter().zip(retained) {
+            if retained {
+                self.analysers.push(analyser);
+            } else {
+                self.cache.remove(analyser.as_ref());
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears all cached values of the group, the caches of the [ResultAnalyser]s are kept.
+    pub fn clear_cache(&mut self) {
+        self.cache = AnalyserGroupCache::new();
+    }
+
+    /// Partitions the routes by `key` and returns one group for each key in the order of their first occurrence.
+    /// Some common keys are provided in [group_key].
+    pub fn group_by<K: PartialEq>(&self, key: impl Fn(&ResultAnalyser<R>) -> K) -> Vec<(K, ResultAnalyserGroup<&A, R>)> {
+        let mut groups: Vec<(K, Vec<&A>)> = vec![];
         for analyser in self.analysers.iter() {
-            total_distance += analyser.as_ref().distance()?;
+            let analyser_key = key(analyser.as_ref());
+            match groups.iter_mut().find(|(group_key, _)| *group_key == analyser_key) {
+                Some((_, analysers)) => analysers.push(analyser),
+                None => groups.push((analyser_key, vec![analyser])),
+            }
         }
 
-        self.cache.total_distance = Some(total_distance);
-        Ok(total_distance)
+        groups.into_iter()
+            .map(|(key, analysers)| (key, ResultAnalyserGroup {
+                analysers,
+                cache: AnalyserGroupCache::new(),
+                _phantom: PhantomData,
+            }))
+            .collect()
     }
 
-    /// Computes the average distance per route.
+    /// Computes `metric` for all routes and combines the values by its [Combine](metric::Combine) rule.
+    /// The combined value is cached until the group is modified, see [push](ResultAnalyserGroup::push).
     ///
     /// Errors will be propagated.
-    pub fn average_distance(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.average_distance {
-            return Ok(*value);
+    pub fn metric<M: Metric>(&mut self, metric: M) -> Result<M::Value, AnalyseError> {
+        if let Some(value) = self.cache.get(&metric) {
+            return Ok(value);
         }
 
-        // analysers.len() can't be zero due to a check on creation.
-        let average_distance = self.total_distance()? / self.analysers.len() as f32;
+        let mut values = vec![];
+        for analyser in self.analysers.iter() {
+            let analyser = analyser.as_ref();
+            values.push((M::COMBINE.weight(analyser)?, metric.compute(analyser)?));
+        }
+
+        let (value, total_weight) = M::COMBINE.combine(values);
+
+        self.cache.insert(metric, value, total_weight);
+        Ok(value)
+    }
+
+    /// Computes the sum of the distance values for all routes.
+    /// For more details see [distance](ResultAnalyser::distance).
+    ///
+    /// Errors will be propagated.
+    pub fn total_distance(&mut self) -> Result<f32, AnalyseError> {
+        self.metric(Distance)
+    }
 
-        self.cache.average_distance = Some(average_distance);
-        Ok(average_distance)
+    /// Computes the average distance per route.
+    ///
+    /// Errors will be propagated.
+    pub fn average_distance(&mut self) -> Result<f32, AnalyseError> {
+        // analysers.len() can't be zero due to a check on creation.
+        Ok(self.total_distance()? / self.analysers.len() as f32)
     }
 
     /// Computes the average speed for all routes including idle times.
@@ -73,88 +178,269 @@ impl<A: AsRef<ResultAnalyser<R>>, R: AsRef<ZusiResult>> ResultAnalyserGroup<A, R
     ///
     /// Errors will be propagated.
     pub fn average_speed(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.average_speed {
-            return Ok(*value);
+        self.metric(AverageSpeed)
+    }
+
+    /// Computes the average speed for all routes excluding idle times.
+    /// For more details see [distance](ResultAnalyser::pure_average_speed).
+    ///
+    /// Errors will be propagated.
+    pub fn pure_average_speed(&mut self, algorithm: PureAverageSpeedAlgorithm) -> Result<f32, AnalyseError> {
+        self.metric(PureAverageSpeed(algorithm))
+    }
+
+    /// Computes the average speed for all routes excluding idle times with all [PureAverageSpeedAlgorithm] variants.
+    /// For more details see [pure_average_speeds](ResultAnalyser::pure_average_speeds).
+    pub fn pure_average_speeds(&mut self) -> PureAverageSpeeds {
+        PureAverageSpeeds {
+            values: PureAverageSpeedAlgorithm::ALL.iter()
+                .map(|algorithm| (*algorithm, self.pure_average_speed(*algorithm)))
+                .collect(),
         }
+    }
 
+    /// Computes the average speed for all routes excluding idle times with the given [StandstillOptions].
+    /// For more details see [pure_average_speed_with_standstill](ResultAnalyser::pure_average_speed_with_standstill).
+    ///
+    /// Errors will be propagated.
+    pub fn pure_average_speed_with_standstill(&mut self, algorithm: PureAverageSpeedAlgorithm, standstill: &StandstillOptions) -> Result<f32, AnalyseError> {
         let mut weighted_speed_sum = 0.;
         for analyser in self.analysers.iter() {
-            weighted_speed_sum += analyser.as_ref().distance()? * analyser.as_ref().average_speed()?;
+            weighted_speed_sum += analyser.as_ref().distance()? * analyser.as_ref().pure_average_speed_with_standstill(algorithm, standstill)?;
         }
 
-        let average_speed = weighted_speed_sum / self.total_distance()?;
+        Ok(weighted_speed_sum / self.total_distance()?)
+    }
 
-        self.cache.average_speed = Some(average_speed);
-        Ok(average_speed)
+    /// Computes the sum of the driving times including idle times for all routes.
+    /// For more details see [distance](ResultAnalyser::driving_time).
+    ///
+    /// Errors will be propagated.
+    pub fn total_driving_time(&mut self) -> Result<Duration, AnalyseError> {
+        self.total_driving_time_with_algorithm(DrivingTimeAlgorithm::FirstToLastEntry)
     }
 
-    /// Computes the average speed for all routes excluding idle times.
-    /// For more details see [distance](ResultAnalyser::pure_average_speed).
+    /// Computes the sum of the driving times including idle times for all routes.
+    /// For more details see [driving_time_with_algorithm](ResultAnalyser::driving_time_with_algorithm).
     ///
     /// Errors will be propagated.
-    pub fn pure_average_speed(&mut self, algorithm: PureAverageSpeedAlgorithm) -> Result<f32, AnalyseError> {
-        match (
-            algorithm,
-            &self.cache.pure_average_speed_by_pure_driving_time,
-            &self.cache.pure_average_speed_by_weighted_local_speeds,
-        ) {
-            (PureAverageSpeedAlgorithm::PureDrivingTime, Some(value), _) => return Ok(*value),
-            (PureAverageSpeedAlgorithm::WeightedLocalSpeeds, _, Some(value)) => return Ok(*value),
-            _ => {}
-        };
+    pub fn total_driving_time_with_algorithm(&mut self, algorithm: DrivingTimeAlgorithm) -> Result<Duration, AnalyseError> {
+        self.metric(DrivingTime(algorithm))
+    }
+
+    /// Computes the sum of the driving times excluding idle times for all routes.
+    /// For more details see [distance](ResultAnalyser::pure_driving_time).
+    ///
+    /// Errors will be propagated.
+    pub fn total_pure_driving_time(&mut self) -> Result<Duration, AnalyseError> {
+        self.metric(PureDrivingTime)
+    }
+
+    /// Computes the sum of the driving times excluding idle times with the given [StandstillOptions] for all routes.
+    /// For more details see [pure_driving_time_with_standstill](ResultAnalyser::pure_driving_time_with_standstill).
+    ///
+    /// Errors will be propagated.
+    pub fn total_pure_driving_time_with_standstill(&self, standstill: &StandstillOptions) -> Result<Duration, AnalyseError> {
+        let mut total_pure_driving_time = Duration::seconds(0);
 
-        let mut weighted_speed_sum = 0.;
         for analyser in self.analysers.iter() {
-            weighted_speed_sum += analyser.as_ref().distance()? * analyser.as_ref().pure_average_speed(algorithm)?;
+            total_pure_driving_time += analyser.as_ref().pure_driving_time_with_standstill(standstill)?;
         }
 
-        let pure_average_speed = weighted_speed_sum / self.total_distance()?;
+        Ok(total_pure_driving_time)
+    }
 
-        match algorithm {
-            PureAverageSpeedAlgorithm::PureDrivingTime =>
-                self.cache.pure_average_speed_by_pure_driving_time = Some(pure_average_speed),
-            PureAverageSpeedAlgorithm::WeightedLocalSpeeds =>
-                self.cache.pure_average_speed_by_weighted_local_speeds = Some(pure_average_speed),
-        }
-        Ok(pure_average_speed)
+    /// Computes the sum of the energy consumptions for all routes.
+    /// For more details see [energy_consumption](ResultAnalyser::energy_consumption).
+    pub fn total_energy_consumption(&mut self) -> f32 {
+        // the energy consumption of a route can't fail
+        self.metric(EnergyConsumption).unwrap()
     }
 
-    /// Computes the sum of the driving times including idle times for all routes.
-    /// For more details see [distance](ResultAnalyser::driving_time).
+    /// Computes the energy consumption per km for all routes.
+    /// For more details see [energy_consumption_per_distance](ResultAnalyser::energy_consumption_per_distance).
     ///
     /// Errors will be propagated.
-    pub fn total_driving_time(&mut self) -> Result<Duration, AnalyseError> {
-        if let Some(value) = &self.cache.total_driving_time {
-            return Ok(*value);
+    pub fn energy_consumption_per_distance(&mut self) -> Result<f32, AnalyseError> {
+        self.metric(EnergyConsumptionPerDistance)
+    }
+
+    /// Computes the energy consumption per hour of driving excluding idle times for all routes,
+    /// which is the total energy consumption divided by the total pure driving time.
+    /// For more details see [energy_consumption_per_pure_driving_time](ResultAnalyser::energy_consumption_per_pure_driving_time).
+    ///
+    /// Errors will be propagated.
+    pub fn energy_consumption_per_pure_driving_time(&mut self) -> Result<f32, AnalyseError> {
+        self.metric(EnergyConsumptionPerPureDrivingTime)
+    }
+
+    /// Computes the time in s spent in each speed band for all routes.
+    /// For more details see [speed_histogram](ResultAnalyser::speed_histogram).
+    ///
+    /// Errors will be propagated.
+    pub fn speed_histogram(&self, histogram_edges: &[f32]) -> Result<Histogram, AnalyseError> {
+        let mut histogram = Histogram::new(histogram_edges.to_vec());
+        for analyser in self.analysers.iter() {
+            histogram.merge(&analyser.as_ref().speed_histogram(histogram_edges)?);
         }
+        Ok(histogram)
+    }
 
-        let mut total_driving_time = Duration::seconds(0);
+    /// Computes the restrictive mode activations and the time and distance spent under each supervision speed for all routes.
+    /// For more details see [train_protection](ResultAnalyser::train_protection).
+    ///
+    /// Errors will be propagated.
+    pub fn train_protection(&self, supervision_speeds: &[f32]) -> Result<SupervisionTotals, AnalyseError> {
+        let mut totals = SupervisionTotals::new(supervision_speeds);
+        for analyser in self.analysers.iter() {
+            totals.merge(&analyser.as_ref().train_protection(supervision_speeds)?.totals);
+        }
+        Ok(totals)
+    }
 
+    /// Counts the forced brakings and emergency stops of all routes.
+    /// For more details see [emergency_braking_counts](ResultAnalyser::emergency_braking_counts).
+    ///
+    /// Errors will be propagated.
+    pub fn emergency_braking_counts(&self, deceleration_threshold: f32) -> Result<HashMap<EmergencyBrakingKind, usize>, AnalyseError> {
+        let mut counts = HashMap::new();
         for analyser in self.analysers.iter() {
-            total_driving_time += analyser.as_ref().driving_time()?;
+            for (kind, count) in analyser.as_ref().emergency_braking_counts(deceleration_threshold)? {
+                *counts.entry(kind).or_insert(0) += count;
+            }
         }
+        Ok(counts)
+    }
 
-        self.cache.total_driving_time = Some(total_driving_time);
-        Ok(total_driving_time)
+    /// Computes `metric` for each route and returns its [Distribution] across the routes.
+    fn distribution(&self, metric: impl Fn(&ResultAnalyser<R>) -> Result<f32, AnalyseError>) -> Result<Distribution, AnalyseError> {
+        let values = self.analysers.iter()
+            .map(|analyser| metric(analyser.as_ref()))
+            .collect::<Result<Vec<f32>, AnalyseError>>()?;
+        // a group always contains at least one analyser
+        Ok(Distribution::new(&values).unwrap())
     }
 
-    /// Computes the sum of the driving times excluding idle times for all routes.
-    /// For more details see [distance](ResultAnalyser::pure_driving_time).
+    /// Computes the distribution of the distance values in m across all routes.
+    /// For more details see [distance](ResultAnalyser::distance).
     ///
     /// Errors will be propagated.
-    pub fn total_pure_driving_time(&mut self) -> Result<Duration, AnalyseError> {
-        if let Some(value) = &self.cache.total_pure_driving_time {
-            return Ok(*value);
+    pub fn distance_distribution(&self) -> Result<Distribution, AnalyseError> {
+        self.distribution(ResultAnalyser::distance)
+    }
+
+    /// Computes the distribution of the driving times in s across all routes.
+    /// For more details see [driving_time](ResultAnalyser::driving_time).
+    ///
+    /// Errors will be propagated.
+    pub fn driving_time_distribution(&self) -> Result<Distribution, AnalyseError> {
+        self.distribution(|analyser| Ok(analyser.driving_time()?.as_seconds_f32()))
+    }
+
+    /// Computes the distribution of the pure driving times in s across all routes.
+    /// For more details see [pure_driving_time](ResultAnalyser::pure_driving_time).
+    ///
+    /// Errors will be propagated.
+    pub fn pure_driving_time_distribution(&self) -> Result<Distribution, AnalyseError> {
+        self.distribution(|analyser| Ok(analyser.pure_driving_time()?.as_seconds_f32()))
+    }
+
+    /// Computes the distribution of the average speeds in m/s across all routes.
+    /// Unlike [average_speed](ResultAnalyserGroup::average_speed), each route has the same weight.
+    /// For more details see [average_speed](ResultAnalyser::average_speed).
+    ///
+    /// Errors will be propagated.
+    pub fn average_speed_distribution(&self) -> Result<Distribution, AnalyseError> {
+        self.distribution(ResultAnalyser::average_speed)
+    }
+
+    /// Computes the distribution of the pure average speeds in m/s across all routes.
+    /// Unlike [pure_average_speed](ResultAnalyserGroup::pure_average_speed), each route has the same weight.
+    /// For more details see [pure_average_speed](ResultAnalyser::pure_average_speed).
+    ///
+    /// Errors will be propagated.
+    pub fn pure_average_speed_distribution(&self, algorithm: PureAverageSpeedAlgorithm) -> Result<Distribution, AnalyseError> {
+        self.distribution(|analyser| analyser.pure_average_speed(algorithm))
+    }
+
+    /// Computes `metric` for each route and splits the values of the successful routes from the failures.
+    fn collect_tolerant<T>(&self, metric: impl Fn(&ResultAnalyser<R>) -> Result<T, AnalyseError>) -> (Vec<T>, Vec<(usize, AnalyseError)>) {
+        let mut values = vec![];
+        let mut failures = vec![];
+        for (index, analyser) in self.analysers.iter().enumerate() {
+            match metric(analyser.as_ref()) {
+                Ok(value) => values.push(value),
+                Err(error) => failures.push((index, error)),
+            }
         }
+        (values, failures)
+    }
 
-        let mut total_pure_driving_time = Duration::seconds(0);
+    /// Computes `metric` like [metric](ResultAnalyserGroup::metric), but skips all routes with errors.
+    /// The value is not cached.
+    pub fn metric_tolerant<M: Metric>(&self, metric: M) -> TolerantAggregate<M::Value> {
+        let (values, failures) = self.collect_tolerant(|analyser| Ok((M::COMBINE.weight(analyser)?, metric.compute(analyser)?)));
+        TolerantAggregate {
+            value: if values.is_empty() { None } else { Some(M::COMBINE.combine(values).0) },
+            failures,
+        }
+    }
 
-        for analyser in self.analysers.iter() {
-            total_pure_driving_time += analyser.as_ref().pure_driving_time()?;
+    /// Computes the sum of the distance values like [total_distance](ResultAnalyserGroup::total_distance),
+    /// but skips all routes with errors.
+    pub fn total_distance_tolerant(&self) -> TolerantAggregate<f32> {
+        self.metric_tolerant(Distance)
+    }
+
+    /// Computes the average distance per route like [average_distance](ResultAnalyserGroup::average_distance),
+    /// but skips all routes with errors.
+    pub fn average_distance_tolerant(&self) -> TolerantAggregate<f32> {
+        let (values, failures) = self.collect_tolerant(ResultAnalyser::distance);
+        TolerantAggregate {
+            value: if values.is_empty() { None } else { Some(values.iter().sum::<f32>() / values.len() as f32) },
+            failures,
         }
+    }
 
-        self.cache.total_pure_driving_time = Some(total_pure_driving_time);
-        Ok(total_pure_driving_time)
+    /// Computes the average speed like [average_speed](ResultAnalyserGroup::average_speed),
+    /// but skips all routes with errors.
+    pub fn average_speed_tolerant(&self) -> TolerantAggregate<f32> {
+        self.metric_tolerant(AverageSpeed)
+    }
+
+    /// Computes the average speed excluding idle times like [pure_average_speed](ResultAnalyserGroup::pure_average_speed),
+    /// but skips all routes with errors.
+    pub fn pure_average_speed_tolerant(&self, algorithm: PureAverageSpeedAlgorithm) -> TolerantAggregate<f32> {
+        self.metric_tolerant(PureAverageSpeed(algorithm))
+    }
+
+    /// Computes the sum of the driving times like [total_driving_time](ResultAnalyserGroup::total_driving_time),
+    /// but skips all routes with errors.
+    pub fn total_driving_time_tolerant(&self) -> TolerantAggregate<Duration> {
+        self.metric_tolerant(DrivingTime(DrivingTimeAlgorithm::FirstToLastEntry))
+    }
+
+    /// Computes the sum of the driving times excluding idle times like [total_pure_driving_time](ResultAnalyserGroup::total_pure_driving_time),
+    /// but skips all routes with errors.
+    pub fn total_pure_driving_time_tolerant(&self) -> TolerantAggregate<Duration> {
+        self.metric_tolerant(PureDrivingTime)
+    }
+
+    /// Checks all routes for suspicious data, the reports are in the same order as the routes.
+    /// For more details see [validate](ResultAnalyser::validate).
+    pub fn validate(&self, options: &ValidationOptions) -> Vec<Result<ValidationReport, AnalyseError>> {
+        self.analysers.iter()
+            .map(|analyser| analyser.as_ref().validate(options))
+            .collect()
+    }
+}
+
+impl<A: AsRef<ResultAnalyser<R>>, R: AsRef<ZusiResult>> Extend<A> for ResultAnalyserGroup<A, R> {
+    /// Adds all routes like [push](ResultAnalyserGroup::push).
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        for analyser in iter {
+            self.push(analyser);
+        }
     }
 }
 
@@ -1,26 +1,156 @@
-use time::Duration;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
 
-#[derive(PartialEq, Debug)]
-pub struct AnalyserGroupCache {
-    pub total_distance: Option<f32>,
-    pub average_distance: Option<f32>,
-    pub average_speed: Option<f32>,
-    pub pure_average_speed_by_pure_driving_time: Option<f32>,
-    pub pure_average_speed_by_weighted_local_speeds: Option<f32>,
-    pub total_driving_time: Option<Duration>,
-    pub total_pure_driving_time: Option<Duration>,
+use crate::result_analyser::ResultAnalyser;
+use crate::result_analyser_group::metric::{Combine, Metric};
+
+/// The combined value of a [Metric] together with the total weight of all routes, which is needed to add a route.
+#[derive(PartialEq, Debug, Copy, Clone)]
+struct CachedValue<V> {
+    value: V,
+    total_weight: f32,
+}
+
+/// All cached values of one [Metric] type.
+trait CacheEntry<R>: Debug {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn eq_entry(&self, other: &dyn CacheEntry<R>) -> bool;
+
+    fn is_empty(&self) -> bool;
+
+    fn add(&mut self, analyser: &ResultAnalyser<R>);
+
+    fn remove(&mut self, analyser: &ResultAnalyser<R>);
+}
+
+/// Returns the cached value including a route added to the group
+/// or [None] if the value has to be recomputed, e.g. because the route has an error which has to be propagated.
+fn added_value<M: Metric, R: AsRef<ZusiResult>>(metric: &M, cached: CachedValue<M::Value>, analyser: &ResultAnalyser<R>) -> Option<CachedValue<M::Value>> {
+    let weight = M::COMBINE.weight(analyser).ok()?;
+    let value = metric.compute(analyser).ok()?;
+    let total_weight = cached.total_weight + weight;
+    match M::COMBINE {
+        Combine::Sum => Some(CachedValue {
+            value: cached.value + value,
+            total_weight,
+        }),
+        _ if cached.total_weight > 0. => Some(CachedValue {
+            value: (cached.value * cached.total_weight + value * weight) / total_weight,
+            total_weight,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the cached value excluding a route removed from the group or [None] if the value has to be recomputed.
+/// Only sums are updated, as removing a route from an average accumulates rounding errors.
+fn removed_value<M: Metric, R: AsRef<ZusiResult>>(metric: &M, cached: CachedValue<M::Value>, analyser: &ResultAnalyser<R>) -> Option<CachedValue<M::Value>> {
+    match M::COMBINE {
+        Combine::Sum => Some(CachedValue {
+            value: cached.value - metric.compute(analyser).ok()?,
+            total_weight: cached.total_weight - 1.,
+        }),
+        _ => None,
+    }
+}
+
+fn update<M: Metric>(entry: &mut HashMap<M, CachedValue<M::Value>>, updated_value: impl Fn(&M, CachedValue<M::Value>) -> Option<CachedValue<M::Value>>) {
+    entry.retain(|metric, cached| match updated_value(metric, *cached) {
+        Some(value) => {
+            *cached = value;
+            true
+        }
+        None => false,
+    });
+}
+
+impl<R: AsRef<ZusiResult>, M: Metric> CacheEntry<R> for HashMap<M, CachedValue<M::Value>> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn eq_entry(&self, other: &dyn CacheEntry<R>) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|other| self == other)
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+
+    fn add(&mut self, analyser: &ResultAnalyser<R>) {
+        update(self, |metric, cached| added_value(metric, cached, analyser));
+    }
+
+    fn remove(&mut self, analyser: &ResultAnalyser<R>) {
+        update(self, |metric, cached| removed_value(metric, cached, analyser));
+    }
+}
+
+/// Memoises the combined values of [Metric]s, keyed by the type and the value of the metric.
+#[derive(Debug)]
+pub struct AnalyserGroupCache<R> {
+    entries: HashMap<TypeId, Box<dyn CacheEntry<R>>>,
 }
 
-impl AnalyserGroupCache {
-    pub fn new() -> AnalyserGroupCache {
+impl<R> AnalyserGroupCache<R> {
+    pub fn new() -> AnalyserGroupCache<R> {
         Self {
-            total_distance: None,
-            average_distance: None,
-            average_speed: None,
-            pure_average_speed_by_pure_driving_time: None,
-            pure_average_speed_by_weighted_local_speeds: None,
-            total_driving_time: None,
-            total_pure_driving_time: None,
+            entries: HashMap::new(),
         }
     }
+
+    pub fn get<M: Metric>(&self, metric: &M) -> Option<M::Value> {
+        self.entries.get(&TypeId::of::<M>())?
+            .as_any()
+            .downcast_ref::<HashMap<M, CachedValue<M::Value>>>()?
+            .get(metric)
+            .map(|cached| cached.value)
+    }
+}
+
+impl<R: AsRef<ZusiResult>> AnalyserGroupCache<R> {
+    pub fn insert<M: Metric>(&mut self, metric: M, value: M::Value, total_weight: f32) {
+        let entry = self.entries.entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::new(HashMap::<M, CachedValue<M::Value>>::new()))
+            .as_any_mut()
+            .downcast_mut::<HashMap<M, CachedValue<M::Value>>>()
+            .expect("entries are keyed by the type of their metric");
+        entry.insert(metric, CachedValue {
+            value,
+            total_weight,
+        });
+    }
+
+    /// Updates the cached values for a route added to the group.
+    pub fn add(&mut self, analyser: &ResultAnalyser<R>) {
+        for entry in self.entries.values_mut() {
+            entry.add(analyser);
+        }
+        self.entries.retain(|_, entry| !entry.is_empty());
+    }
+
+    /// Updates the cached values for a route removed from the group.
+    pub fn remove(&mut self, analyser: &ResultAnalyser<R>) {
+        for entry in self.entries.values_mut() {
+            entry.remove(analyser);
+        }
+        self.entries.retain(|_, entry| !entry.is_empty());
+    }
+}
+
+impl<R> PartialEq for AnalyserGroupCache<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries.len() == other.entries.len()
+            && self.entries.iter().all(|(type_id, entry)| other.entries.get(type_id)
+                .is_some_and(|other_entry| entry.eq_entry(other_entry.as_ref())))
+    }
 }
@@ -0,0 +1,96 @@
+/// Distribution of a metric across the routes of a group.
+///
+/// Quantiles are interpolated linearly between the two nearest values.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Distribution {
+    /// The smallest value and the index of its route.
+    pub min: (usize, f32),
+    /// The largest value and the index of its route.
+    pub max: (usize, f32),
+    pub median: f32,
+    pub mean: f32,
+    /// Population standard deviation.
+    pub standard_deviation: f32,
+    sorted_values: Vec<f32>,
+}
+
+impl Distribution {
+    /// Creates the distribution of the values of all routes in the order of the group.
+    ///
+    /// Returns [None] if there are no values.
+    pub fn new(values: &[f32]) -> Option<Distribution> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut indexed_values: Vec<(usize, f32)> = values.iter().copied().enumerate().collect();
+        indexed_values.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        let sorted_values: Vec<f32> = indexed_values.iter().map(|(_, value)| *value).collect();
+
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / values.len() as f32;
+
+        let mut distribution = Self {
+            min: indexed_values[0],
+            max: indexed_values[indexed_values.len() - 1],
+            median: 0.,
+            mean,
+            standard_deviation: variance.sqrt(),
+            sorted_values,
+        };
+        distribution.median = distribution.quantile(0.5);
+        Some(distribution)
+    }
+
+    /// Returns the value below which the fraction `q` of the values lies.
+    ///
+    /// Panics if `q` is not between 0 and 1.
+    pub fn quantile(&self, q: f32) -> f32 {
+        assert!((0. ..=1.).contains(&q), "quantile must be between 0 and 1");
+        let position = q * (self.sorted_values.len() - 1) as f32;
+        let lower = position.floor() as usize;
+        let upper = position.ceil() as usize;
+        let fraction = position - lower as f32;
+        self.sorted_values[lower] + (self.sorted_values[upper] - self.sorted_values[lower]) * fraction
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted_values.len()
+    }
+
+    /// Always false, as a distribution can't be created without values.
+    pub fn is_empty(&self) -> bool {
+        self.sorted_values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribution() {
+        let distribution = Distribution::new(&[4., 1., 7., 2.]).unwrap();
+
+        assert_eq!(distribution.min, (1, 1.));
+        assert_eq!(distribution.max, (2, 7.));
+        assert_eq!(distribution.median, 3.);
+        assert_eq!(distribution.mean, 3.5);
+        assert_eq!(distribution.standard_deviation, 2.291288);
+        assert_eq!(distribution.quantile(0.), 1.);
+        assert_eq!(distribution.quantile(0.25), 1.75);
+        assert_eq!(distribution.quantile(1.), 7.);
+        assert_eq!(distribution.len(), 4);
+    }
+
+    #[test]
+    fn test_distribution_empty() {
+        assert_eq!(Distribution::new(&[]), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quantile_out_of_range() {
+        Distribution::new(&[1.]).unwrap().quantile(1.5);
+    }
+}
@@ -0,0 +1,45 @@
+//! Key functions for [group_by](crate::result_analyser_group::ResultAnalyserGroup::group_by).
+//!
+//! All dates are taken from the recording date (`Datum`) of the [ZusiResult] and not from the simulated time.
+
+use time::{Date, Month, Weekday};
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::result_analyser::section::find_station_indices;
+use crate::result_analyser::ResultAnalyser;
+
+pub fn zugnummer<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> String {
+    analyser.result().zugnummer.clone()
+}
+
+pub fn day<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> Date {
+    analyser.result().datum.date()
+}
+
+/// Returns the ISO year and the ISO week.
+pub fn week<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> (i32, u8) {
+    let (year, week, _) = analyser.result().datum.date().to_iso_week_date();
+    (year, week)
+}
+
+pub fn month<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> (i32, Month) {
+    let date = analyser.result().datum.date();
+    (date.year(), date.month())
+}
+
+pub fn weekday<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> Weekday {
+    analyser.result().datum.weekday()
+}
+
+/// Returns the names of all stations in the order they have been passed,
+/// so runs on the same route with the same stops have the same fingerprint.
+/// Stations are identified like in [sections](ResultAnalyser::sections).
+pub fn route_fingerprint<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> Vec<String> {
+    let result = analyser.result();
+    find_station_indices(result, &analyser.options().validity).into_iter()
+        .map(|index| {
+            let ResultValue::FahrtEintrag(fahrt_eintrag) = &result.value[index];
+            fahrt_eintrag.fahrt_text.clone()
+        })
+        .collect()
+}
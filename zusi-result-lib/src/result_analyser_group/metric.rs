@@ -0,0 +1,183 @@
+//! Metrics of a single route which can be combined for all routes of a [ResultAnalyserGroup](crate::result_analyser_group::ResultAnalyserGroup).
+//!
+//! Any type implementing [Metric] can be computed and cached by
+//! [metric](crate::result_analyser_group::ResultAnalyserGroup::metric).
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Sub};
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::result_analyser::{AnalyseError, DrivingTimeAlgorithm, PureAverageSpeedAlgorithm, ResultAnalyser};
+
+/// Rule to combine the values of all routes of a group.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Combine {
+    Sum,
+    /// Average of the values with the same weight for each route.
+    Mean,
+    /// Average of the values weighted by the [distance](ResultAnalyser::distance) of each route.
+    DistanceWeighted,
+    /// Average of the values weighted by the [pure_driving_time](ResultAnalyser::pure_driving_time) of each route.
+    TimeWeighted,
+}
+
+/// Value of a [Metric] which can be combined by all [Combine] rules.
+pub trait MetricValue: Copy + PartialEq + Debug + Default + Add<Output = Self> + Sub<Output = Self>
+    + Mul<f32, Output = Self> + Div<f32, Output = Self> + 'static {}
+
+impl<T> MetricValue for T where T: Copy + PartialEq + Debug + Default + Add<Output = T> + Sub<Output = T>
+    + Mul<f32, Output = T> + Div<f32, Output = T> + 'static {}
+
+/// A value computed for each route and combined for a group.
+///
+/// Metrics are cached by their value, so parameters like an algorithm are part of the identity of a metric.
+pub trait Metric: Copy + Eq + Hash + Debug + 'static {
+    type Value: MetricValue;
+
+    const COMBINE: Combine;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<Self::Value, AnalyseError>;
+}
+
+/// See [distance](ResultAnalyser::distance).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct Distance;
+
+impl Metric for Distance {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::Sum;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.distance()
+    }
+}
+
+/// See [average_speed](ResultAnalyser::average_speed).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct AverageSpeed;
+
+impl Metric for AverageSpeed {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::DistanceWeighted;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.average_speed()
+    }
+}
+
+/// See [pure_average_speed](ResultAnalyser::pure_average_speed).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct PureAverageSpeed(pub PureAverageSpeedAlgorithm);
+
+impl Metric for PureAverageSpeed {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::DistanceWeighted;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.pure_average_speed(self.0)
+    }
+}
+
+/// See [driving_time_with_algorithm](ResultAnalyser::driving_time_with_algorithm).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct DrivingTime(pub DrivingTimeAlgorithm);
+
+impl Metric for DrivingTime {
+    type Value = Duration;
+
+    const COMBINE: Combine = Combine::Sum;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<Duration, AnalyseError> {
+        analyser.driving_time_with_algorithm(self.0)
+    }
+}
+
+/// See [pure_driving_time](ResultAnalyser::pure_driving_time).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct PureDrivingTime;
+
+impl Metric for PureDrivingTime {
+    type Value = Duration;
+
+    const COMBINE: Combine = Combine::Sum;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<Duration, AnalyseError> {
+        analyser.pure_driving_time()
+    }
+}
+
+/// See [energy_consumption](ResultAnalyser::energy_consumption).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct EnergyConsumption;
+
+impl Metric for EnergyConsumption {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::Sum;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        Ok(analyser.energy_consumption())
+    }
+}
+
+/// See [energy_consumption_per_distance](ResultAnalyser::energy_consumption_per_distance).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct EnergyConsumptionPerDistance;
+
+impl Metric for EnergyConsumptionPerDistance {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::DistanceWeighted;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.energy_consumption_per_distance()
+    }
+}
+
+/// See [energy_consumption_per_pure_driving_time](ResultAnalyser::energy_consumption_per_pure_driving_time).
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct EnergyConsumptionPerPureDrivingTime;
+
+impl Metric for EnergyConsumptionPerPureDrivingTime {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::TimeWeighted;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.energy_consumption_per_pure_driving_time()
+    }
+}
+
+impl Combine {
+    /// Returns the weight of a route for this rule, which is 1 for [Combine::Sum] and [Combine::Mean].
+    pub(crate) fn weight<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        match self {
+            Combine::Sum | Combine::Mean => Ok(1.),
+            Combine::DistanceWeighted => analyser.distance(),
+            Combine::TimeWeighted => Ok(analyser.pure_driving_time()?.as_seconds_f32()),
+        }
+    }
+
+    /// Combines the weights and values of the routes and returns the combined value together with the total weight.
+    pub(crate) fn combine<V: MetricValue>(&self, values: impl IntoIterator<Item = (f32, V)>) -> (V, f32) {
+        let mut sum = V::default();
+        let mut total_weight = 0.;
+        for (weight, value) in values {
+            sum = match self {
+                Combine::Sum => sum + value,
+                _ => sum + value * weight,
+            };
+            total_weight += weight;
+        }
+
+        match self {
+            Combine::Sum => (sum, total_weight),
+            _ => (sum / total_weight, total_weight),
+        }
+    }
+}
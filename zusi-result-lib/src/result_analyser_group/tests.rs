@@ -1,10 +1,17 @@
-use time::Duration;
-use time::macros::datetime;
+use std::collections::HashMap;
+use time::{Duration, Month, Weekday};
+use time::macros::{date, datetime};
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
 use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
 
-use crate::result_analyser::{AnalyseError, PureAverageSpeedAlgorithm, ResultAnalyser};
-use crate::result_analyser_group::{CreateAnalyserGroupError, ResultAnalyserGroup};
+use crate::result_analyser::analysis_options::StandstillOptions;
+use crate::result_analyser::emergency_braking::{EmergencyBrakingKind, DEFAULT_EMERGENCY_DECELERATION};
+use crate::result_analyser::train_protection::DEFAULT_SUPERVISION_SPEEDS;
+use crate::result_analyser::validation::ValidationOptions;
+use crate::result_analyser::{AnalyseError, DrivingTimeAlgorithm, PureAverageSpeedAlgorithm, ResultAnalyser};
+use crate::result_analyser_group::{group_key, CreateAnalyserGroupError, RemoveAnalyserError, ResultAnalyserGroup, TolerantAggregate};
+use crate::result_analyser_group::analyser_group_cache::AnalyserGroupCache;
+use crate::result_analyser_group::metric::{AverageSpeed, Combine, Distance, DrivingTime, EnergyConsumption, EnergyConsumptionPerPureDrivingTime, Metric, PureDrivingTime};
 
 #[test]
 fn test_caching() {
@@ -468,6 +475,46 @@ fn test_total_driving_time_with_error() {
     );
 }
 
+#[test]
+fn test_total_driving_time_excluding_gaps() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:23))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-02 3:23))
+                .build()),
+        ])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(
+        analyser_group.total_driving_time_with_algorithm(DrivingTimeAlgorithm::ExcludingGaps(Duration::minutes(30))).unwrap(),
+        Duration::minutes(15),
+    );
+    assert_eq!(analyser_group.total_driving_time().unwrap(), Duration::minutes(255));
+}
+
 #[test]
 fn test_total_pure_driving_time() {
     let result1 = ZusiResult::builder()
@@ -542,6 +589,1162 @@ fn test_total_pure_driving_time_with_error() {
     );
 }
 
+#[test]
+fn test_energy_consumption() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(36_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:30))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(54_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(3000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:15))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.total_energy_consumption(), 25.);
+    assert_eq!(analyser_group.energy_consumption_per_distance().unwrap(), 5.);
+    assert_eq!(analyser_group.energy_consumption_per_pure_driving_time().unwrap(), 33.333332);
+}
+
+#[test]
+fn test_energy_consumption_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.total_energy_consumption(), 2.);
+    assert_eq!(
+        analyser_group.energy_consumption_per_distance(),
+        Err(AnalyseError::NoEntries)
+    );
+}
+
+#[test]
+fn test_speed_histogram() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:01))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:02))
+                .fahrt_speed(30.)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.speed_histogram(&[15.]).unwrap().weights, vec![60., 120.]);
+}
+
+#[test]
+fn test_speed_histogram_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(
+        analyser_group.speed_histogram(&[15.]),
+        Err(AnalyseError::NoEntries)
+    );
+}
+
+#[test]
+fn test_validate() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    let reports = analyser_group.validate(&ValidationOptions::default());
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].as_ref().unwrap().anomalies.len(), 1);
+    assert_eq!(reports[1], Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_pure_driving_time_with_standstill() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(0.05)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(0.05)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let standstill = StandstillOptions {
+        speed_threshold: 0.1,
+        min_duration: Duration::ZERO,
+    };
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.total_pure_driving_time().unwrap(), Duration::minutes(4));
+    assert_eq!(analyser_group.total_pure_driving_time_with_standstill(&standstill).unwrap(), Duration::minutes(3));
+    assert_eq!(analyser_group.pure_average_speed_with_standstill(PureAverageSpeedAlgorithm::PureDrivingTime, &standstill).unwrap(), 10.);
+}
+
+#[test]
+fn test_pure_driving_time_with_standstill_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.total_pure_driving_time_with_standstill(&StandstillOptions::default()), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.pure_average_speed_with_standstill(PureAverageSpeedAlgorithm::PureDrivingTime, &StandstillOptions::default()), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_pure_average_speeds() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.pure_average_speed(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds).unwrap(), 12.142857);
+    assert_eq!(analyser_group.pure_average_speed(PureAverageSpeedAlgorithm::MedianLocalSpeed).unwrap(), 10.);
+
+    let pure_average_speeds = analyser_group.pure_average_speeds();
+    assert_eq!(pure_average_speeds.get(PureAverageSpeedAlgorithm::PureDrivingTime), Some(Ok(10.)));
+    assert_eq!(pure_average_speeds.get(PureAverageSpeedAlgorithm::HarmonicLocalSpeeds), Some(Ok(12.142857)));
+}
+
+#[test]
+fn test_pure_average_speeds_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    let pure_average_speeds = analyser_group.pure_average_speeds();
+    assert!(pure_average_speeds.values.iter().all(|(_, value)| *value == Err(AnalyseError::NoEntries)));
+    assert_eq!(pure_average_speeds.spread(), None);
+}
+
+#[test]
+fn test_train_protection() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(23.6111)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(45.8333)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    let totals = analyser_group.train_protection(&DEFAULT_SUPERVISION_SPEEDS).unwrap();
+    assert_eq!(totals.restrictive_activations, 3);
+    assert_eq!(
+        totals.totals.iter().map(|total| (total.duration, total.distance)).collect::<Vec<_>>(),
+        vec![
+            (Duration::seconds(40), 400.),
+            (Duration::seconds(10), 100.),
+            (Duration::ZERO, 0.),
+            (Duration::ZERO, 0.),
+        ],
+    );
+}
+
+#[test]
+fn test_train_protection_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(12.5)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(300.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(10.)
+                .fahrt_speed_zugsicherung(45.8333)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.train_protection(&DEFAULT_SUPERVISION_SPEEDS), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_emergency_braking_counts() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(1)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(250.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(350.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.emergency_braking_counts(DEFAULT_EMERGENCY_DECELERATION).unwrap(), HashMap::from([
+        (EmergencyBrakingKind::ForcedBraking, 1),
+        (EmergencyBrakingKind::EmergencyStop, 1),
+    ]));
+}
+
+#[test]
+fn test_emergency_braking_counts_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:00))
+                .fahrt_speed(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:10))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(1)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:20))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.emergency_braking_counts(DEFAULT_EMERGENCY_DECELERATION), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_tolerant_aggregates() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 10:22:18))
+                .fahrt_speed(8.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(410.)
+                .fahrt_zeit(datetime!(2019-01-01 10:23:08))
+                .fahrt_speed(8.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+    let result3 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 10:22:18))
+                .fahrt_speed(32.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(810.)
+                .fahrt_zeit(datetime!(2019-01-01 10:22:43))
+                .fahrt_speed(32.)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+        ResultAnalyser::new(result3),
+    ]).unwrap();
+    let failures = vec![(1, AnalyseError::NoEntries)];
+
+    assert_eq!(analyser_group.total_distance_tolerant(), TolerantAggregate { value: Some(1200.), failures: failures.clone() });
+    assert_eq!(analyser_group.average_distance_tolerant(), TolerantAggregate { value: Some(600.), failures: failures.clone() });
+    assert_eq!(analyser_group.average_speed_tolerant(), TolerantAggregate { value: Some(24.), failures: failures.clone() });
+    assert_eq!(
+        analyser_group.pure_average_speed_tolerant(PureAverageSpeedAlgorithm::PureDrivingTime),
+        TolerantAggregate { value: Some(24.), failures: failures.clone() },
+    );
+    assert_eq!(analyser_group.total_driving_time_tolerant(), TolerantAggregate { value: Some(Duration::seconds(75)), failures: failures.clone() });
+    assert_eq!(analyser_group.total_pure_driving_time_tolerant(), TolerantAggregate { value: Some(Duration::seconds(75)), failures });
+}
+
+#[test]
+fn test_tolerant_aggregates_all_failing() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(10.)
+                .fahrt_zeit(datetime!(2019-01-01 10:22:18))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.total_distance_tolerant(), TolerantAggregate {
+        value: Some(0.),
+        failures: vec![(0, AnalyseError::NoEntries)],
+    });
+    assert_eq!(analyser_group.average_speed_tolerant(), TolerantAggregate {
+        value: None,
+        failures: vec![(0, AnalyseError::NoEntries), (1, AnalyseError::ZeroDrivingTime)],
+    });
+}
+
+#[test]
+fn test_group_by() {
+    let result1 = ZusiResult::builder()
+        .zugnummer("20001".into())
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.0)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("A".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("B".into())
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .zugnummer("20002".into())
+        .datum(datetime!(2019-01-07 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.0)
+                .fahrt_zeit(datetime!(2019-01-07 23:18))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("A".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-07 23:28))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("C".into())
+                .build()),
+        ])
+        .build();
+    let result3 = ZusiResult::builder()
+        .zugnummer("20001".into())
+        .datum(datetime!(2019-02-04 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.0)
+                .fahrt_zeit(datetime!(2019-02-04 23:18))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("A".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(900.)
+                .fahrt_zeit(datetime!(2019-02-04 23:28))
+                .fahrt_speed(10.)
+                .fahrt_typ(2)
+                .fahrt_text("B".into())
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+        ResultAnalyser::new(result3),
+    ]).unwrap();
+
+    let groups = analyser_group.group_by(group_key::zugnummer);
+    assert_eq!(groups.len(), 2);
+    let (key, mut group) = groups.into_iter().next().unwrap();
+    assert_eq!(key, "20001");
+    assert_eq!(group.total_distance().unwrap(), 1500.);
+    assert_eq!(group.average_speed().unwrap(), 1.3);
+
+    let groups = analyser_group.group_by(group_key::route_fingerprint);
+    let keys: Vec<Vec<String>> = groups.iter().map(|(key, _)| key.clone()).collect();
+    assert_eq!(keys, vec![vec!["A".to_string(), "B".to_string()], vec!["A".to_string(), "C".to_string()]]);
+
+    let groups = analyser_group.group_by(group_key::month);
+    let keys: Vec<(i32, Month)> = groups.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec![(2019, Month::January), (2019, Month::February)]);
+    let (_, mut group) = groups.into_iter().next().unwrap();
+    assert_eq!(group.total_distance().unwrap(), 1800.);
+
+    let groups = analyser_group.group_by(group_key::week);
+    let keys: Vec<(i32, u8)> = groups.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec![(2019, 1), (2019, 2), (2019, 6)]);
+
+    let groups = analyser_group.group_by(group_key::weekday);
+    let keys: Vec<Weekday> = groups.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec![Weekday::Tuesday, Weekday::Monday]);
+
+    let groups = analyser_group.group_by(group_key::day);
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[1].0, date!(2019-01-07));
+}
+
+#[test]
+fn test_distributions() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:38))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result3 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+        ResultAnalyser::new(result3),
+    ]).unwrap();
+
+    let distance = analyser_group.distance_distribution().unwrap();
+    assert_eq!(distance.min, (0, 600.));
+    assert_eq!(distance.max, (2, 1200.));
+    assert_eq!(distance.median, 600.);
+    assert_eq!(distance.mean, 800.);
+
+    let driving_time = analyser_group.driving_time_distribution().unwrap();
+    assert_eq!(driving_time.max, (1, 1200.));
+    assert_eq!(driving_time.quantile(0.75), 900.);
+    assert_eq!(analyser_group.pure_driving_time_distribution().unwrap(), driving_time);
+
+    let average_speed = analyser_group.average_speed_distribution().unwrap();
+    assert_eq!(average_speed.min, (1, 0.5));
+    assert_eq!(average_speed.max, (2, 2.));
+    assert_eq!(average_speed.median, 1.);
+    assert_eq!(average_speed.mean, 7. / 6.);
+    assert_eq!(average_speed.standard_deviation, 0.6236096);
+    assert_eq!(analyser_group.pure_average_speed_distribution(PureAverageSpeedAlgorithm::PureDrivingTime).unwrap(), average_speed);
+}
+
+#[test]
+fn test_distributions_with_error() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.distance_distribution(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.average_speed_distribution(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_push() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+    ]).unwrap();
+    assert_eq!(analyser_group.total_distance().unwrap(), 600.);
+    assert_eq!(analyser_group.average_distance().unwrap(), 600.);
+    assert_eq!(analyser_group.average_speed().unwrap(), 1.);
+    assert_eq!(analyser_group.total_driving_time().unwrap(), Duration::minutes(10));
+
+    analyser_group.push(ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()));
+
+    // the cached values have been updated without being cleared
+    assert_eq!(analyser_group.cache.get(&Distance), Some(1800.));
+    assert_eq!(analyser_group.cache.get(&AverageSpeed), Some(1.6666666));
+    assert_eq!(analyser_group.cache.get(&DrivingTime(DrivingTimeAlgorithm::FirstToLastEntry)), Some(Duration::minutes(20)));
+    assert_eq!(analyser_group.cache.get(&PureDrivingTime), None);
+
+    let mut recomputed_group = ResultAnalyserGroup::new(analyser_group.analysers().iter().collect()).unwrap();
+    let recomputed = (
+        recomputed_group.total_distance(),
+        recomputed_group.average_distance(),
+        recomputed_group.average_speed(),
+        recomputed_group.total_driving_time(),
+        recomputed_group.total_pure_driving_time(),
+    );
+    assert_eq!(recomputed, (
+        analyser_group.total_distance(),
+        analyser_group.average_distance(),
+        analyser_group.average_speed(),
+        analyser_group.total_driving_time(),
+        analyser_group.total_pure_driving_time(),
+    ));
+}
+
+#[test]
+fn test_push_energy_consumption_per_pure_driving_time() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(36_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:30))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+    ]).unwrap();
+    assert_eq!(analyser_group.energy_consumption_per_pure_driving_time().unwrap(), 20.);
+
+    analyser_group.push(ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(54_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(3000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:15))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()));
+
+    // the cached value is weighted by the pure driving time of the routes, not averaged per route
+    assert_eq!(analyser_group.cache.get(&EnergyConsumptionPerPureDrivingTime), Some(33.333332));
+
+    let mut recomputed_group = ResultAnalyserGroup::new(analyser_group.analysers().iter().collect()).unwrap();
+    assert_eq!(
+        recomputed_group.energy_consumption_per_pure_driving_time().unwrap(),
+        analyser_group.energy_consumption_per_pure_driving_time().unwrap(),
+    );
+}
+
+#[test]
+fn test_push_with_error() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+    ]).unwrap();
+    assert_eq!(analyser_group.total_distance().unwrap(), 600.);
+    assert_eq!(analyser_group.average_speed().unwrap(), 1.);
+
+    analyser_group.extend([ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build())]);
+
+    assert_eq!(analyser_group.total_distance(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.average_speed(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.analysers().len(), 2);
+}
+
+#[test]
+fn test_remove_and_retain() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build()),
+    ]).unwrap();
+    assert_eq!(analyser_group.total_distance(), Err(AnalyseError::NoEntries));
+
+    let removed = analyser_group.remove(2).unwrap();
+    assert_eq!(removed.distance(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.total_distance().unwrap(), 1800.);
+    assert_eq!(analyser_group.average_speed().unwrap(), 1.6666666);
+    assert_eq!(analyser_group.total_energy_consumption(), 0.);
+
+    analyser_group.retain(|analyser| analyser.distance().unwrap() > 1000.).unwrap();
+
+    assert_eq!(analyser_group.cache.get(&Distance), Some(1200.));
+    assert_eq!(analyser_group.cache.get(&AverageSpeed), None);
+    assert_eq!(analyser_group.cache.get(&EnergyConsumption), Some(0.));
+    assert_eq!(analyser_group.average_speed().unwrap(), 2.);
+
+    assert_eq!(analyser_group.retain(|_| false), Err(RemoveAnalyserError::NoAnalysersLeft));
+    assert_eq!(analyser_group.remove(0), Err(RemoveAnalyserError::NoAnalysersLeft));
+    assert_eq!(analyser_group.analysers().len(), 1);
+}
+
+#[test]
+fn test_clear_cache() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+    ]).unwrap();
+    assert_eq!(analyser_group.total_distance().unwrap(), 600.);
+
+    analyser_group.clear_cache();
+    assert_eq!(analyser_group.cache, AnalyserGroupCache::new());
+    assert_eq!(analyser_group.total_distance().unwrap(), 600.);
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+struct EntryCount;
+
+impl Metric for EntryCount {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::Mean;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        Ok(analyser.result().value.len() as f32)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+struct TimeWeightedSpeed;
+
+impl Metric for TimeWeightedSpeed {
+    type Value = f32;
+
+    const COMBINE: Combine = Combine::TimeWeighted;
+
+    fn compute<R: AsRef<ZusiResult>>(&self, analyser: &ResultAnalyser<R>) -> Result<f32, AnalyseError> {
+        analyser.average_speed()
+    }
+}
+
+#[test]
+fn test_custom_metric() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+        ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1200.)
+                .fahrt_zeit(datetime!(2019-01-01 23:48))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.metric(EntryCount).unwrap(), 2.);
+    assert_eq!(analyser_group.metric(TimeWeightedSpeed).unwrap(), 0.75);
+    assert_eq!(analyser_group.cache.get(&EntryCount), Some(2.));
+    assert_eq!(analyser_group.cache.get(&TimeWeightedSpeed), Some(0.75));
+
+    analyser_group.push(ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(600.)
+                .fahrt_zeit(datetime!(2019-01-01 23:23))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1800.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .fahrt_speed(10.)
+                .build()),
+        ])
+        .build()));
+
+    // both values have been updated without being cleared
+    assert_eq!(analyser_group.cache.get(&EntryCount), Some(2.3333333));
+    assert_eq!(analyser_group.cache.get(&TimeWeightedSpeed), Some(1.2));
+
+    analyser_group.push(ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build()));
+
+    assert_eq!(analyser_group.cache.get(&EntryCount), Some(1.75));
+    assert_eq!(analyser_group.metric(TimeWeightedSpeed), Err(AnalyseError::NoEntries));
+
+    let tolerant = analyser_group.metric_tolerant(TimeWeightedSpeed);
+    assert_eq!(tolerant.value, Some(1.2));
+    assert_eq!(tolerant.failures, vec![(3, AnalyseError::NoEntries)]);
+}
+
 #[test]
 fn test_try_from_results() {
     let result = ZusiResult::builder()